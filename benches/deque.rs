@@ -0,0 +1,119 @@
+use criterion::{
+    black_box, criterion_group, criterion_main, measurement::WallTime, AxisScale, BenchmarkGroup,
+    BenchmarkId, Criterion, PlotConfiguration, Throughput,
+};
+use purely_functional_data_structure::deque::{
+    bankers_deque::BankersDequeC, lazy_rebuilding_deque::LazyRebuildingDequeC,
+    real_time_deque::RealTimeDequeC, Deque,
+};
+use rand::prelude::*;
+
+#[derive(Clone, Copy)]
+enum Op {
+    Cons,
+    Snoc,
+    Tail,
+    Init,
+}
+
+/// fill from the rear and drain from the front, rebalancing all the way
+fn snoc_tail<D: Deque<u64>>(n: u64) -> D {
+    let mut d = (0..n).fold(D::empty(), |d, x| d.snoc(x));
+    while let Some(e) = d.tail() {
+        d = e;
+    }
+    d
+}
+
+fn run<D: Deque<u64>>(ops: &[Op]) -> D {
+    ops.iter()
+        .enumerate()
+        .fold(D::empty(), |d, (x, op)| match op {
+            Op::Cons => D::cons(x as u64, &d),
+            Op::Snoc => d.snoc(x as u64),
+            Op::Tail => d.tail().unwrap_or(d),
+            Op::Init => d.init().unwrap_or(d),
+        })
+}
+
+/// random operations with one end growing and the other shrinking in alternate phases
+fn gen_random(n: usize) -> Vec<Op> {
+    let mut rng = StdRng::seed_from_u64(100);
+    (0..n)
+        .map(|i| {
+            let front = rng.gen_bool(if i / 100 % 2 == 0 { 0.8 } else { 0.2 });
+            match (rng.gen_bool(0.6), front) {
+                (true, true) => Op::Cons,
+                (true, false) => Op::Snoc,
+                (false, true) => Op::Tail,
+                (false, false) => Op::Init,
+            }
+        })
+        .collect()
+}
+
+macro_rules! bn_balance {
+    ($bn:ident) => {
+        $bn!(BankersDequeC, 2);
+        $bn!(BankersDequeC, 3);
+        $bn!(BankersDequeC, 4);
+        $bn!(BankersDequeC, 8);
+        $bn!(RealTimeDequeC, 2);
+        $bn!(RealTimeDequeC, 3);
+        $bn!(RealTimeDequeC, 4);
+        $bn!(LazyRebuildingDequeC, 2);
+        $bn!(LazyRebuildingDequeC, 3);
+        $bn!(LazyRebuildingDequeC, 4);
+        $bn!(LazyRebuildingDequeC, 8);
+    };
+}
+
+fn benchmark_snoc_tail(g: &mut BenchmarkGroup<WallTime>, n: u64) {
+    g.throughput(Throughput::Elements(n));
+    macro_rules! bn {
+        ($deque:ident, $c:literal) => {
+            g.bench_with_input(
+                BenchmarkId::new(concat!(stringify!($deque), "<", $c, ">"), n),
+                &n,
+                |b, &n| b.iter(|| snoc_tail::<$deque<u64, $c>>(black_box(n))),
+            );
+        };
+    }
+    bn_balance!(bn);
+}
+
+fn benchmark_random(g: &mut BenchmarkGroup<WallTime>, ops: &[Op]) {
+    g.throughput(Throughput::Elements(ops.len() as u64));
+    macro_rules! bn {
+        ($deque:ident, $c:literal) => {
+            g.bench_with_input(
+                BenchmarkId::new(concat!(stringify!($deque), "<", $c, ">"), ops.len()),
+                &ops,
+                |b, ops| b.iter(|| run::<$deque<u64, $c>>(black_box(ops))),
+            );
+        };
+    }
+    bn_balance!(bn);
+}
+
+pub fn benchmark_deques(c: &mut Criterion) {
+    let mut g = c.benchmark_group("snoc_tail");
+    g.plot_config(PlotConfiguration::default().summary_scale(AxisScale::Logarithmic));
+
+    for n in [10, 100, 1000, 10000] {
+        benchmark_snoc_tail(&mut g, n);
+    }
+    g.finish();
+
+    let mut g = c.benchmark_group("random");
+    g.plot_config(PlotConfiguration::default().summary_scale(AxisScale::Logarithmic));
+
+    for n in [10, 100, 1000, 10000] {
+        let ops = gen_random(n);
+        benchmark_random(&mut g, &ops);
+    }
+    g.finish();
+}
+
+criterion_group!(benches, benchmark_deques);
+criterion_main!(benches);
@@ -3,9 +3,11 @@ use criterion::{
     BenchmarkId, Criterion, PlotConfiguration, Throughput,
 };
 use purely_functional_data_structure::heap::{
-    binomial_heap::BinomialHeap, lazy_binomial_heap::LazyBinomialHeap,
-    lazy_pairing_heap::LazyPairingHeap, leftist_heap::LeftistHeap, pairing_heap::PairingHeap,
-    scheduled_binomial_heap::ScheduledBinomialHeap, splay_heap::SplayHeap, Heap,
+    binomial_heap::BinomialHeap, bootstrapped_heap::BootstrappedHeap, explicit_min::ExplicitMin,
+    lazy_binomial_heap::LazyBinomialHeap, lazy_pairing_heap::LazyPairingHeap,
+    leftist_heap::LeftistHeap, pairing_heap::PairingHeap,
+    scheduled_binomial_heap::ScheduledBinomialHeap, skew_binomial_heap::SkewBinomialHeap,
+    splay_heap::SplayHeap, weight_biased_leftist_heap::WeightBiasedLeftistHeap, Heap,
 };
 use rand::prelude::*;
 
@@ -29,15 +31,23 @@ fn benchmark_insert(g: &mut BenchmarkGroup<WallTime>, xs: &[u64]) {
                 &xs,
                 |b, xs| b.iter(|| insert::<$heap<u64>>(black_box(xs))),
             );
+            g.bench_with_input(
+                BenchmarkId::new(concat!("ExplicitMin<", stringify!($heap), ">"), xs.len()),
+                &xs,
+                |b, xs| b.iter(|| insert::<ExplicitMin<u64, $heap<u64>>>(black_box(xs))),
+            );
         };
     }
     bn!(LeftistHeap);
+    bn!(WeightBiasedLeftistHeap);
     bn!(BinomialHeap);
     bn!(PairingHeap);
     bn!(SplayHeap);
     bn!(LazyBinomialHeap);
     bn!(LazyPairingHeap);
     bn!(ScheduledBinomialHeap);
+    bn!(SkewBinomialHeap);
+    bn!(BootstrappedHeap);
 }
 
 fn benchmark_find_min(g: &mut BenchmarkGroup<WallTime>, xs: &[u64]) {
@@ -48,15 +58,24 @@ fn benchmark_find_min(g: &mut BenchmarkGroup<WallTime>, xs: &[u64]) {
             g.bench_with_input(BenchmarkId::new(stringify!($heap), xs.len()), &h, |b, h| {
                 b.iter(|| h.find_min())
             });
+            let h = insert::<ExplicitMin<u64, $heap<u64>>>(xs);
+            g.bench_with_input(
+                BenchmarkId::new(concat!("ExplicitMin<", stringify!($heap), ">"), xs.len()),
+                &h,
+                |b, h| b.iter(|| h.find_min()),
+            );
         };
     }
     bn!(LeftistHeap);
+    bn!(WeightBiasedLeftistHeap);
     bn!(BinomialHeap);
     bn!(PairingHeap);
     bn!(SplayHeap);
     bn!(LazyBinomialHeap);
     bn!(LazyPairingHeap);
     bn!(ScheduledBinomialHeap);
+    bn!(SkewBinomialHeap);
+    bn!(BootstrappedHeap);
 }
 
 fn benchmark_delete_min(g: &mut BenchmarkGroup<WallTime>, xs: &[u64]) {
@@ -67,15 +86,24 @@ fn benchmark_delete_min(g: &mut BenchmarkGroup<WallTime>, xs: &[u64]) {
             g.bench_with_input(BenchmarkId::new(stringify!($heap), xs.len()), &h, |b, h| {
                 b.iter(|| h.delete_min())
             });
+            let h = insert::<ExplicitMin<u64, $heap<u64>>>(xs);
+            g.bench_with_input(
+                BenchmarkId::new(concat!("ExplicitMin<", stringify!($heap), ">"), xs.len()),
+                &h,
+                |b, h| b.iter(|| h.delete_min()),
+            );
         };
     }
     bn!(LeftistHeap);
+    bn!(WeightBiasedLeftistHeap);
     bn!(BinomialHeap);
     bn!(PairingHeap);
     bn!(SplayHeap);
     bn!(LazyBinomialHeap);
     bn!(LazyPairingHeap);
     bn!(ScheduledBinomialHeap);
+    bn!(SkewBinomialHeap);
+    bn!(BootstrappedHeap);
 }
 
 pub fn benchmark_heaps(c: &mut Criterion) {
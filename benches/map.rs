@@ -0,0 +1,81 @@
+use criterion::{
+    black_box, criterion_group, criterion_main, measurement::WallTime, AxisScale, BenchmarkGroup,
+    BenchmarkId, Criterion, PlotConfiguration, Throughput,
+};
+use purely_functional_data_structure::{
+    hamt::HamtMap,
+    int_map::IntMap,
+    tree::{FiniteMap, Tree},
+};
+use rand::prelude::*;
+
+type TreeMap<K, V> = Tree<(K, V)>;
+
+fn bind<M: FiniteMap<u64, u64>>(xs: &[u64]) -> M {
+    xs.iter().fold(M::empty(), |m, &x| m.bind(x, x))
+}
+
+fn lookup<M: FiniteMap<u64, u64>>(m: &M, xs: &[u64]) -> usize {
+    xs.iter().filter(|&&x| m.lookup(x).is_some()).count()
+}
+
+fn gen_random(n: usize) -> Vec<u64> {
+    let mut rng = StdRng::seed_from_u64(100);
+    (0..n).map(|_| rng.gen()).collect()
+}
+
+fn benchmark_bind(g: &mut BenchmarkGroup<WallTime>, xs: &[u64]) {
+    g.throughput(Throughput::Elements(xs.len() as u64));
+    macro_rules! bn {
+        ($map:ident) => {
+            g.bench_with_input(
+                BenchmarkId::new(stringify!($map), xs.len()),
+                &xs,
+                |b, xs| b.iter(|| bind::<$map<u64, u64>>(black_box(xs))),
+            );
+        };
+    }
+    bn!(TreeMap);
+    bn!(HamtMap);
+    g.bench_with_input(BenchmarkId::new("IntMap", xs.len()), &xs, |b, xs| {
+        b.iter(|| bind::<IntMap<u64>>(black_box(xs)))
+    });
+}
+
+fn benchmark_lookup(g: &mut BenchmarkGroup<WallTime>, xs: &[u64]) {
+    g.throughput(Throughput::Elements(xs.len() as u64));
+    macro_rules! bn {
+        ($map:ty, $name:expr) => {
+            let m = bind::<$map>(xs);
+            g.bench_with_input(BenchmarkId::new($name, xs.len()), &m, |b, m| {
+                b.iter(|| lookup(m, black_box(xs)))
+            });
+        };
+    }
+    bn!(TreeMap<u64, u64>, "TreeMap");
+    bn!(HamtMap<u64, u64>, "HamtMap");
+    bn!(IntMap<u64>, "IntMap");
+}
+
+pub fn benchmark_maps(c: &mut Criterion) {
+    let mut g = c.benchmark_group("bind_random");
+    g.plot_config(PlotConfiguration::default().summary_scale(AxisScale::Logarithmic));
+
+    for n in [10, 100, 1000, 10000, 100000] {
+        let xs = gen_random(n);
+        benchmark_bind(&mut g, &xs);
+    }
+    g.finish();
+
+    let mut g = c.benchmark_group("lookup_random");
+    g.plot_config(PlotConfiguration::default().summary_scale(AxisScale::Logarithmic));
+
+    for n in [10, 100, 1000, 10000, 100000] {
+        let xs = gen_random(n);
+        benchmark_lookup(&mut g, &xs);
+    }
+    g.finish();
+}
+
+criterion_group!(benches, benchmark_maps);
+criterion_main!(benches);
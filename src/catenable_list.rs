@@ -0,0 +1,225 @@
+use std::sync::Arc;
+
+use crate::{
+    lazy,
+    lazy::Lazy,
+    queue::{bankers_queue::BankersQueueFamily, Queue, QueueFamily},
+};
+
+/// Catenable list by structural bootstrapping (section 10.2.1): a head element and a
+/// queue of suspended sublists. `cons`, `snoc`, `append` and `head` take O(1) and `tail`
+/// takes O(1) amortized time with any queue family `F`.
+pub struct CatenableList<T, F = BankersQueueFamily>(Arc<Cell<T, F>>)
+where
+    T: Clone + 'static,
+    F: QueueFamily;
+
+enum Cell<T, F>
+where
+    T: Clone + 'static,
+    F: QueueFamily,
+{
+    Nil,
+    Cons(T, F::Queue<Lazy<CatenableList<T, F>>>),
+}
+
+// implement manually because derive impl requires F to be Clone
+impl<T: Clone + 'static, F: QueueFamily> Clone for CatenableList<T, F> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T: Clone + 'static, F: QueueFamily> From<Cell<T, F>> for CatenableList<T, F> {
+    fn from(x: Cell<T, F>) -> Self {
+        Self(Arc::new(x))
+    }
+}
+
+impl<T: Clone + 'static> CatenableList<T> {
+    /// empty list over `BankersQueue`; use `CatenableList::new` for other queues
+    pub fn empty() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone + 'static, F: QueueFamily> Default for CatenableList<T, F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone + 'static, F: QueueFamily> CatenableList<T, F> {
+    pub fn new() -> Self {
+        Cell::Nil.into()
+    }
+    pub fn is_empty(&self) -> bool {
+        matches!(self.0.as_ref(), Cell::Nil)
+    }
+    pub fn singleton(x: T) -> Self {
+        Cell::Cons(x, F::Queue::empty()).into()
+    }
+    /// both lists are non-empty
+    fn link(&self, s: Lazy<Self>) -> Self {
+        match self.0.as_ref() {
+            Cell::Cons(x, q) => Cell::Cons(x.clone(), q.snoc(s)).into(),
+            Cell::Nil => unreachable!(),
+        }
+    }
+    /// `q` is non-empty
+    fn link_all(q: F::Queue<Lazy<Self>>) -> Self {
+        let t = q.head().unwrap().force();
+        let q = q.tail().unwrap();
+        if q.is_empty() {
+            t
+        } else {
+            t.link(lazy!(Self::link_all(q)))
+        }
+    }
+    pub fn append(&self, other: &Self) -> Self {
+        if self.is_empty() {
+            other.clone()
+        } else if other.is_empty() {
+            self.clone()
+        } else {
+            self.link(Lazy::value(other.clone()))
+        }
+    }
+    pub fn cons(x: T, xs: &Self) -> Self {
+        Self::singleton(x).append(xs)
+    }
+    pub fn snoc(&self, x: T) -> Self {
+        self.append(&Self::singleton(x))
+    }
+    pub fn head(&self) -> Option<T> {
+        match self.0.as_ref() {
+            Cell::Nil => None,
+            Cell::Cons(x, _) => Some(x.clone()),
+        }
+    }
+    pub fn tail(&self) -> Option<Self> {
+        match self.0.as_ref() {
+            Cell::Nil => None,
+            Cell::Cons(_, q) if q.is_empty() => Some(Self::new()),
+            Cell::Cons(_, q) => Some(Self::link_all(q.clone())),
+        }
+    }
+}
+
+impl<T: Clone + 'static, F: QueueFamily> CatenableList<T, F> {
+    /// move out the queue of sublists if no other list shares this cell, leaving `Nil`
+    fn take_queue(&mut self) -> Option<F::Queue<Lazy<Self>>> {
+        match std::mem::replace(Arc::get_mut(&mut self.0)?, Cell::Nil) {
+            Cell::Nil => None,
+            Cell::Cons(_, q) => Some(q),
+        }
+    }
+}
+
+/// `cons` and right-nested `append` nest one sublist per element and `snoc` queues one per
+/// element, so drop them with an explicit stack of queues popped one sublist at a time
+/// instead of recursing into them. Popping stops at a sublist shared with a live list,
+/// which leaves a shared queue after one step.
+impl<T: Clone + 'static, F: QueueFamily> Drop for CatenableList<T, F> {
+    fn drop(&mut self) {
+        let mut queues = self.take_queue().into_iter().collect::<Vec<_>>();
+        while let Some(q) = queues.pop() {
+            let Some((s, rest)) = q.uncons() else {
+                continue;
+            };
+            // a lazy tail may hold the cell of `s` until the next head is forced
+            drop(q);
+            rest.head();
+            if let Some(mut xs) = s.into_value() {
+                queues.push(rest);
+                queues.extend(xs.take_queue());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::queue::real_time_queue::RealTimeQueueFamily;
+    use rand::prelude::*;
+
+    fn to_vec<F: QueueFamily>(xs: &CatenableList<i32, F>) -> Vec<i32> {
+        let mut xs = xs.clone();
+        let mut v = vec![];
+        while let Some(x) = xs.head() {
+            v.push(x);
+            xs = xs.tail().unwrap();
+        }
+        v
+    }
+
+    #[test]
+    fn test() {
+        let xs = (0..5).fold(CatenableList::empty(), |xs, x| xs.snoc(x));
+        let ys = (5..10)
+            .rev()
+            .fold(CatenableList::empty(), |ys, y| CatenableList::cons(y, &ys));
+        let zs = xs.append(&ys);
+        assert_eq!(to_vec(&zs), (0..10).collect::<Vec<_>>());
+        assert_eq!(to_vec(&zs.append(&xs)).len(), 15);
+        // persistent
+        assert_eq!(to_vec(&xs), (0..5).collect::<Vec<_>>());
+        assert!(CatenableList::<i32>::empty().tail().is_none());
+    }
+
+    fn random<F: QueueFamily>(seed: u64) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut pool = vec![(CatenableList::<i32, F>::new(), vec![])];
+        for x in 0..3000 {
+            let (mut xs, mut v) = pool[rng.gen_range(0..pool.len())].clone();
+            let (ys, w) = &pool[rng.gen_range(0..pool.len())];
+            match rng.gen_range(0..4) {
+                0 => {
+                    xs = CatenableList::cons(x, &xs);
+                    v.insert(0, x);
+                }
+                1 => {
+                    xs = xs.snoc(x);
+                    v.push(x);
+                }
+                2 if !v.is_empty() => {
+                    xs = xs.tail().unwrap();
+                    v.remove(0);
+                }
+                3 if v.len() + w.len() < 1000 => {
+                    xs = xs.append(ys);
+                    v.extend(w);
+                }
+                _ => continue,
+            }
+            assert_eq!(xs.head(), v.first().copied());
+            pool.push((xs, v));
+        }
+        for (xs, v) in pool.iter().step_by(50) {
+            assert_eq!(&to_vec(xs), v);
+        }
+    }
+
+    #[test]
+    fn test_drop_long() {
+        let xs = (0..100000).fold(CatenableList::empty(), |xs, x| CatenableList::cons(x, &xs));
+        assert_eq!(xs.head(), Some(99999));
+        let ys = (0..100000).fold(CatenableList::empty(), |ys, y| {
+            CatenableList::singleton(y).append(&ys.snoc(y))
+        });
+        assert_eq!(ys.head(), Some(99999));
+        let zs = (0..1000000).fold(CatenableList::empty(), |zs, z| zs.snoc(z));
+        assert_eq!(zs.head(), Some(0));
+        let ws = (0..500000).fold(CatenableList::empty(), |ws, w| {
+            ws.append(&CatenableList::singleton(w).snoc(w))
+        });
+        assert_eq!(ws.head(), Some(0));
+    }
+
+    #[test]
+    fn test_random() {
+        random::<BankersQueueFamily>(10);
+        random::<RealTimeQueueFamily>(11);
+    }
+}
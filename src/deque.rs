@@ -1,10 +1,18 @@
 pub mod bankers_deque;
+pub mod global_rebuilding_deque;
+pub mod implicit_catenable_deque;
+pub mod lazy_rebuilding_deque;
 pub mod native_deque;
+pub mod real_time_catenable_deque;
 pub mod real_time_deque;
+pub mod simple_catenable_deque;
+
+use anyhow::{bail, Result};
+use std::marker::PhantomData;
 
 pub trait Deque<T>
 where
-    Self: Sized,
+    Self: Sized + Clone,
 {
     fn empty() -> Self;
     fn is_empty(&self) -> bool;
@@ -14,37 +22,406 @@ where
     fn snoc(&self, x: T) -> Self;
     fn last(&self) -> Option<T>;
     fn init(&self) -> Option<Self>;
+
+    /// number of elements, O(n) by traversal unless overridden
+    fn len(&self) -> usize {
+        self.iter().count()
+    }
+    /// elements from the head, or from the last by `rev`, without draining
+    fn iter(&self) -> impl DoubleEndedIterator<Item = T> {
+        Iter(self.clone(), PhantomData)
+    }
+    /// `i`-th element from the head, walking from the nearer end
+    fn lookup(&self, i: usize) -> Option<T> {
+        let n = self.len();
+        if i >= n {
+            None
+        } else if i < n - i {
+            self.iter().nth(i)
+        } else {
+            self.iter().nth_back(n - 1 - i)
+        }
+    }
+    /// alias of `lookup`
+    fn get(&self, i: usize) -> Option<T> {
+        self.lookup(i)
+    }
+    /// replace the `i`-th element, rebuilding from the nearer end
+    fn update(&self, i: usize, x: T) -> Result<Self> {
+        let n = self.len();
+        if i >= n {
+            bail!("invalid index");
+        }
+        let mut d = self.clone();
+        let mut xs = vec![];
+        if i < n - i {
+            for _ in 0..i {
+                xs.push(d.head().unwrap());
+                d = d.tail().unwrap();
+            }
+            let d = Self::cons(x, &d.tail().unwrap());
+            Ok(xs.into_iter().rev().fold(d, |d, y| Self::cons(y, &d)))
+        } else {
+            for _ in i + 1..n {
+                xs.push(d.last().unwrap());
+                d = d.init().unwrap();
+            }
+            let d = d.init().unwrap().snoc(x);
+            Ok(xs.into_iter().rev().fold(d, |d, y| d.snoc(y)))
+        }
+    }
+    /// the elements in the reverse order
+    fn rev(&self) -> Self {
+        self.iter().fold(Self::empty(), |d, x| Self::cons(x, &d))
+    }
+    /// the first `i` elements and the rest, moving the shorter part
+    fn split_at(&self, i: usize) -> (Self, Self) {
+        let n = self.len();
+        let i = i.min(n);
+        if i <= n - i {
+            let front = self.iter().take(i).fold(Self::empty(), |d, x| d.snoc(x));
+            let back = (0..i).fold(self.clone(), |d, _| d.tail().unwrap());
+            (front, back)
+        } else {
+            let back = self
+                .iter()
+                .rev()
+                .take(n - i)
+                .fold(Self::empty(), |d, x| Self::cons(x, &d));
+            let front = (i..n).fold(self.clone(), |d, _| d.init().unwrap());
+            (front, back)
+        }
+    }
+    /// elements of `self` followed by those of `other`, moving the shorter one
+    fn append(&self, other: &Self) -> Self {
+        if self.len() >= other.len() {
+            other.iter().fold(self.clone(), |d, x| d.snoc(x))
+        } else {
+            self.iter()
+                .rev()
+                .fold(other.clone(), |d, x| Self::cons(x, &d))
+        }
+    }
+}
+
+/// Deque with efficient concatenation (chapter 11), keeping the other operations within
+/// O(log n) amortized. Implementors route `Deque::append` to `CatenableDeque::append`.
+pub trait CatenableDeque<T>: Deque<T> {
+    /// elements of `self` followed by those of `other` in O(1) amortized whatever the sizes,
+    /// against O(min(m, n)) for the default `Deque::append`
+    fn append(&self, other: &Self) -> Self;
+}
+
+/// iterator of `Deque::iter` popping either end of a copy
+struct Iter<T, D>(D, PhantomData<T>);
+
+impl<T, D: Deque<T>> Iterator for Iter<T, D> {
+    type Item = T;
+    fn next(&mut self) -> Option<T> {
+        let x = self.0.head()?;
+        self.0 = self.0.tail()?;
+        Some(x)
+    }
+}
+
+impl<T, D: Deque<T>> DoubleEndedIterator for Iter<T, D> {
+    fn next_back(&mut self) -> Option<T> {
+        let x = self.0.last()?;
+        self.0 = self.0.init()?;
+        Some(x)
+    }
 }
 
 macro_rules! impl_deque {
     ($d:ident) => {
+        $crate::deque::impl_deque!($d, {});
+    };
+    ($d:ident, { $($body:tt)* }) => {
         impl<T: Clone + 'static> $crate::deque::Deque<T> for $d<T> {
-            fn empty() -> Self {
-                $d::empty()
-            }
-            fn is_empty(&self) -> bool {
-                $d::is_empty(self)
-            }
-            fn cons(x: T, xs: &Self) -> Self {
-                $d::cons(x, xs)
-            }
-            fn head(&self) -> Option<T> {
-                $d::head(self)
-            }
-            fn tail(&self) -> Option<Self> {
-                $d::tail(self)
-            }
-            fn snoc(&self, x: T) -> Self {
-                $d::snoc(self, x)
-            }
-            fn last(&self) -> Option<T> {
-                $d::last(self)
-            }
-            fn init(&self) -> Option<Self> {
-                $d::init(self)
-            }
+            $crate::deque::impl_deque!(@methods $d);
+            $($body)*
+        }
+    };
+    ($d:ident<const $c:ident>) => {
+        $crate::deque::impl_deque!($d<const $c>, {});
+    };
+    ($d:ident<const $c:ident>, { $($body:tt)* }) => {
+        impl<T: Clone + 'static, const $c: usize> $crate::deque::Deque<T> for $d<T, $c> {
+            $crate::deque::impl_deque!(@methods $d);
+            $($body)*
+        }
+    };
+    (@methods $d:ident) => {
+        fn empty() -> Self {
+            $d::empty()
+        }
+        fn is_empty(&self) -> bool {
+            $d::is_empty(self)
+        }
+        fn cons(x: T, xs: &Self) -> Self {
+            $d::cons(x, xs)
+        }
+        fn head(&self) -> Option<T> {
+            $d::head(self)
+        }
+        fn tail(&self) -> Option<Self> {
+            $d::tail(self)
+        }
+        fn snoc(&self, x: T) -> Self {
+            $d::snoc(self, x)
+        }
+        fn last(&self) -> Option<T> {
+            $d::last(self)
+        }
+        fn init(&self) -> Option<Self> {
+            $d::init(self)
         }
     };
 }
 
 pub(crate) use impl_deque;
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        bankers_deque::{BankersDeque, BankersDequeC},
+        global_rebuilding_deque::GlobalRebuildingDeque,
+        implicit_catenable_deque::ImplicitCatenableDeque,
+        lazy_rebuilding_deque::{LazyRebuildingDeque, LazyRebuildingDequeC},
+        native_deque::NaiveDeque,
+        real_time_catenable_deque::RealTimeCatenableDeque,
+        real_time_deque::{RealTimeDeque, RealTimeDequeC},
+        simple_catenable_deque::SimpleCatenableDeque,
+        *,
+    };
+    use rand::prelude::*;
+
+    fn from_iter<D: Deque<i32>>(xs: impl IntoIterator<Item = i32>) -> D {
+        xs.into_iter().fold(D::empty(), |d, x| d.snoc(x))
+    }
+
+    fn to_vec<D: Deque<i32>>(d: &D) -> Vec<i32> {
+        d.iter().collect()
+    }
+
+    fn check<D: Deque<i32>>() {
+        // elements pushed on both ends are split between the front and the rear
+        let d = (0..10)
+            .rev()
+            .fold(from_iter::<D>(10..20), |d, x| D::cons(x, &d));
+        assert_eq!(d.len(), 20);
+        assert_eq!(to_vec(&d), (0..20).collect::<Vec<_>>());
+        assert_eq!(
+            d.iter().rev().collect::<Vec<_>>(),
+            (0..20).rev().collect::<Vec<_>>()
+        );
+        // both ends of one iterator meet in the middle
+        let mut it = d.iter();
+        let mut xs = vec![];
+        while let Some(x) = it.next() {
+            xs.push(x);
+            xs.extend(it.next_back());
+        }
+        assert_eq!(xs.len(), 20);
+        assert_eq!(&xs[..4], [0, 19, 1, 18]);
+
+        for i in 0..20 {
+            assert_eq!(d.lookup(i as usize), Some(i));
+            assert_eq!(d.get(i as usize), Some(i));
+        }
+        assert_eq!(d.lookup(20), None);
+        assert_eq!(d.get(20), None);
+        for i in 0..20 {
+            let e = d.update(i, 100).unwrap();
+            let mut v = (0..20).collect::<Vec<_>>();
+            v[i] = 100;
+            assert_eq!(to_vec(&e), v);
+        }
+        assert!(d.update(20, 100).is_err());
+        assert_eq!(to_vec(&d), (0..20).collect::<Vec<_>>());
+
+        assert_eq!(to_vec(&d.rev()), (0..20).rev().collect::<Vec<_>>());
+        assert_eq!(d.rev().head(), Some(19));
+        assert_eq!(d.rev().rev().last(), Some(19));
+
+        for i in 0..=21 {
+            let (front, back) = d.split_at(i);
+            let i = i.min(20) as i32;
+            assert_eq!(to_vec(&front), (0..i).collect::<Vec<_>>());
+            assert_eq!(to_vec(&back), (i..20).collect::<Vec<_>>());
+            assert_eq!(front.len() + back.len(), 20);
+            // the parts are deques of their own
+            assert_eq!(front.snoc(-1).last(), Some(-1));
+            assert_eq!(D::cons(-1, &back).head(), Some(-1));
+            assert_eq!(to_vec(&front.append(&back)), (0..20).collect::<Vec<_>>());
+        }
+        let e = from_iter::<D>(20..23);
+        assert_eq!(to_vec(&d.append(&e)), (0..23).collect::<Vec<_>>());
+        assert_eq!(
+            to_vec(&e.append(&d)),
+            (20..23).chain(0..20).collect::<Vec<_>>()
+        );
+        assert_eq!(d.append(&D::empty()).len(), 20);
+        assert_eq!(D::empty().append(&d).len(), 20);
+        assert_eq!(D::empty().len(), 0);
+        assert!(D::empty().iter().next_back().is_none());
+    }
+
+    #[test]
+    fn test_defaults() {
+        check::<BankersDeque<_>>();
+        check::<BankersDequeC<_, 3>>();
+        check::<BankersDequeC<_, 4>>();
+        check::<BankersDequeC<_, 8>>();
+        check::<GlobalRebuildingDeque<_>>();
+        check::<ImplicitCatenableDeque<_>>();
+        check::<LazyRebuildingDeque<_>>();
+        check::<LazyRebuildingDequeC<_, 3>>();
+        check::<LazyRebuildingDequeC<_, 8>>();
+        check::<NaiveDeque<_>>();
+        check::<RealTimeCatenableDeque<_>>();
+        // the real-time schedules keep up only with a balance constant of at most 4
+        check::<RealTimeDeque<_>>();
+        check::<RealTimeDequeC<_, 3>>();
+        check::<RealTimeDequeC<_, 4>>();
+        check::<SimpleCatenableDeque<_>>();
+    }
+
+    #[test]
+    fn test_alternating_ends() {
+        // an iterator switching ends at every step must not reverse everything left each time
+        fn check<D: Deque<i32>>() {
+            let n = 20000;
+            let d = from_iter::<D>(0..n);
+            let mut it = d.iter();
+            let (mut i, mut j) = (0, n);
+            while let Some(x) = it.next() {
+                assert_eq!(x, i);
+                i += 1;
+                if let Some(y) = it.next_back() {
+                    j -= 1;
+                    assert_eq!(y, j);
+                }
+            }
+            assert_eq!(i, j);
+        }
+        check::<BankersDeque<_>>();
+        check::<RealTimeDeque<_>>();
+    }
+
+    fn check_catenable<D: CatenableDeque<i32>>() {
+        // out of reach for appends linear in the smaller side
+        let mut d = from_iter::<D>(0..4);
+        for _ in 0..40 {
+            d = CatenableDeque::append(&d, &d);
+        }
+        assert_eq!((d.head(), d.last()), (Some(0), Some(3)));
+        let d = d.tail().unwrap().init().unwrap();
+        assert_eq!((d.head(), d.last()), (Some(1), Some(2)));
+    }
+
+    #[test]
+    fn test_catenable() {
+        check_catenable::<ImplicitCatenableDeque<_>>();
+        check_catenable::<RealTimeCatenableDeque<_>>();
+        check_catenable::<SimpleCatenableDeque<_>>();
+    }
+
+    /// random operations on persistent versions, compared with `NaiveDeque` as the model
+    fn check_against_naive<D: Deque<i32>>(seed: u64) {
+        check_against_naive_with::<D>(seed, |_| {})
+    }
+
+    /// `check_against_naive` also checking `invariant` of every version. Most operations
+    /// continue from the latest version, and pushes and pops favour one end in alternate
+    /// phases, so that one side runs out while the other grows.
+    pub(super) fn check_against_naive_with<D: Deque<i32>>(seed: u64, invariant: impl Fn(&D)) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let p = [rng.gen_range(0.0..1.0), rng.gen_range(0.0..1.0)];
+        let mut pool = vec![(D::empty(), NaiveDeque::empty())];
+        for x in 0..2000 {
+            let (d, m) = if rng.gen_bool(0.8) {
+                pool.last().unwrap().clone()
+            } else {
+                pool.choose(&mut rng).unwrap().clone()
+            };
+            let front = rng.gen_bool(p[x as usize / 250 % 2]);
+            let (d, m) = match rng.gen_range(0..8) {
+                0 | 1 if front => (D::cons(x, &d), NaiveDeque::cons(x, &m)),
+                0 | 1 => (d.snoc(x), m.snoc(x)),
+                2 | 3 if front => match (d.tail(), m.tail()) {
+                    (Some(d), Some(m)) => (d, m),
+                    (d, m) => {
+                        assert_eq!(d.is_none(), m.is_none());
+                        continue;
+                    }
+                },
+                2 | 3 => match (d.init(), m.init()) {
+                    (Some(d), Some(m)) => (d, m),
+                    (d, m) => {
+                        assert_eq!(d.is_none(), m.is_none());
+                        continue;
+                    }
+                },
+                4 => {
+                    let i = rng.gen_range(0..=m.len());
+                    match (d.update(i, x), m.update(i, x)) {
+                        (Ok(d), Ok(m)) => (d, m),
+                        (d, m) => {
+                            assert!(d.is_err() && m.is_err());
+                            continue;
+                        }
+                    }
+                }
+                5 => (d.rev(), m.rev()),
+                6 => {
+                    let i = rng.gen_range(0..=m.len() + 1);
+                    let ((d1, d2), (m1, m2)) = (d.split_at(i), m.split_at(i));
+                    pool.push((d1, m1));
+                    (d2, m2)
+                }
+                _ => {
+                    let (e, n) = pool.choose(&mut rng).unwrap();
+                    if m.len() + n.len() > 500 {
+                        continue;
+                    }
+                    (d.append(e), m.append(n))
+                }
+            };
+            invariant(&d);
+            assert_eq!(d.is_empty(), m.is_empty());
+            assert_eq!(d.len(), m.len());
+            assert_eq!(d.head(), m.head());
+            assert_eq!(d.last(), m.last());
+            let i = rng.gen_range(0..=m.len());
+            assert_eq!(d.lookup(i), m.lookup(i));
+            assert_eq!(to_vec(&d), to_vec(&m));
+            assert_eq!(
+                d.iter().rev().collect::<Vec<_>>(),
+                m.iter().rev().collect::<Vec<_>>()
+            );
+            pool.push((d, m));
+        }
+    }
+
+    #[test]
+    fn test_against_naive() {
+        // each seed also draws how lopsided the phases are; `RealTimeCatenableDeque` runs
+        // this with its invariant among its own tests
+        for seed in 50..54 {
+            check_against_naive::<BankersDeque<_>>(seed);
+            check_against_naive::<BankersDequeC<_, 3>>(seed);
+            check_against_naive::<BankersDequeC<_, 4>>(seed);
+            check_against_naive::<BankersDequeC<_, 8>>(seed);
+            check_against_naive::<GlobalRebuildingDeque<_>>(seed);
+            check_against_naive::<ImplicitCatenableDeque<_>>(seed);
+            check_against_naive::<LazyRebuildingDeque<_>>(seed);
+            check_against_naive::<LazyRebuildingDequeC<_, 3>>(seed);
+            check_against_naive::<LazyRebuildingDequeC<_, 8>>(seed);
+            check_against_naive::<RealTimeDeque<_>>(seed);
+            check_against_naive::<RealTimeDequeC<_, 3>>(seed);
+            check_against_naive::<RealTimeDequeC<_, 4>>(seed);
+            check_against_naive::<SimpleCatenableDeque<_>>(seed);
+        }
+    }
+}
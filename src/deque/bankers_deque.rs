@@ -1,3 +1,5 @@
+use anyhow::{bail, Result};
+
 use crate::{
     lazy,
     lazy::{Stream, StreamCell},
@@ -5,15 +7,28 @@ use crate::{
 
 use super::impl_deque;
 
+/// Banker's deque (section 8.4.2) keeping each of the front and the rear at most `C` times
+/// as long as the other plus one
 #[derive(Clone)]
-pub struct BankersDeque<T> {
+pub struct BankersDequeC<T, const C: usize> {
     lenf: usize,
     f: Stream<T>,
     lenr: usize,
     r: Stream<T>,
 }
 
-impl<T: Clone + 'static> BankersDeque<T> {
+/// `BankersDequeC` with the balance constant 2 of the book
+pub type BankersDeque<T> = BankersDequeC<T, 2>;
+
+impl<T: Clone + 'static, const C: usize> BankersDequeC<T, C> {
+    /// `C`, rejected at compile time unless at least 2
+    const BALANCE: usize = {
+        assert!(
+            C >= 2,
+            "the balance constant of BankersDeque must be at least 2"
+        );
+        C
+    };
     pub fn empty() -> Self {
         Self {
             lenf: 0,
@@ -25,8 +40,12 @@ impl<T: Clone + 'static> BankersDeque<T> {
     pub fn is_empty(&self) -> bool {
         self.lenf + self.lenr == 0
     }
+    /// O(1)
+    pub fn len(&self) -> usize {
+        self.lenf + self.lenr
+    }
     fn check(self) -> Self {
-        let c = 2;
+        let c = Self::BALANCE;
         if self.lenf > c * self.lenr + 1 {
             let i = (self.lenf + self.lenr) / 2;
             let j = self.lenf + self.lenr - i;
@@ -117,9 +136,187 @@ impl<T: Clone + 'static> BankersDeque<T> {
             ),
         }
     }
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = T> {
+        Iter {
+            lenf: self.lenf,
+            f: self.f.clone(),
+            lenr: self.lenr,
+            r: self.r.clone(),
+        }
+    }
+    /// O(min(i, n - i)) walking the front or the rear
+    pub fn lookup(&self, i: usize) -> Option<T> {
+        if i < self.lenf {
+            self.f.iter().nth(i)
+        } else if i < self.len() {
+            self.r.iter().nth(self.len() - 1 - i)
+        } else {
+            None
+        }
+    }
+    pub fn update(&self, i: usize, x: T) -> Result<Self> {
+        if i < self.lenf {
+            Ok(Self {
+                f: self.f.update(i, x)?,
+                ..self.clone()
+            })
+        } else if i < self.len() {
+            Ok(Self {
+                r: self.r.update(self.len() - 1 - i, x)?,
+                ..self.clone()
+            })
+        } else {
+            bail!("invalid index")
+        }
+    }
+    /// O(1) by swapping the front and the rear
+    pub fn rev(&self) -> Self {
+        Self {
+            lenf: self.lenr,
+            f: self.r.clone(),
+            lenr: self.lenf,
+            r: self.f.clone(),
+        }
+    }
+    /// O(1), suspending the split of the list it falls in and the rebalancing of both parts
+    pub fn split_at(&self, i: usize) -> (Self, Self) {
+        let n = self.len();
+        let i = i.min(n);
+        if i <= self.lenf {
+            let front = Self {
+                lenf: i,
+                f: self.f.take(i),
+                lenr: 0,
+                r: Stream::empty(),
+            };
+            let back = Self {
+                lenf: self.lenf - i,
+                f: self.f.drop(i),
+                lenr: self.lenr,
+                r: self.r.clone(),
+            };
+            (front.check(), back.check())
+        } else {
+            let k = n - i;
+            let front = Self {
+                lenf: self.lenf,
+                f: self.f.clone(),
+                lenr: self.lenr - k,
+                r: self.r.drop(k),
+            };
+            let back = Self {
+                lenf: 0,
+                f: Stream::empty(),
+                lenr: k,
+                r: self.r.take(k),
+            };
+            (front.check(), back.check())
+        }
+    }
+    /// O(1), suspending the reversal of the rear of `self` and the front of `other`
+    pub fn append(&self, other: &Self) -> Self {
+        Self {
+            lenf: self.len(),
+            f: self.f.extend(&self.r.reverse()),
+            lenr: other.len(),
+            r: other.r.extend(&other.f.reverse()),
+        }
+        .check()
+    }
+}
+
+impl_deque!(BankersDequeC<const C>, {
+    fn len(&self) -> usize {
+        BankersDequeC::len(self)
+    }
+    fn iter(&self) -> impl DoubleEndedIterator<Item = T> {
+        BankersDequeC::iter(self)
+    }
+    fn lookup(&self, i: usize) -> Option<T> {
+        BankersDequeC::lookup(self, i)
+    }
+    fn update(&self, i: usize, x: T) -> Result<Self> {
+        BankersDequeC::update(self, i, x)
+    }
+    fn rev(&self) -> Self {
+        BankersDequeC::rev(self)
+    }
+    fn split_at(&self, i: usize) -> (Self, Self) {
+        BankersDequeC::split_at(self, i)
+    }
+    fn append(&self, other: &Self) -> Self {
+        BankersDequeC::append(self, other)
+    }
+});
+
+/// iterator over a front and a rear stream of known lengths, splitting the rest of one in
+/// half once the other runs out so that alternating ends stays amortized O(1) as in
+/// `NaiveDeque`
+pub(super) struct Iter<T> {
+    pub(super) lenf: usize,
+    pub(super) f: Stream<T>,
+    pub(super) lenr: usize,
+    pub(super) r: Stream<T>,
+}
+
+/// pop the head of a stream known to be non-empty
+fn pop<T: Clone + 'static>(s: &mut Stream<T>) -> T {
+    match s.force() {
+        StreamCell::Cons(x, xs) => {
+            *s = xs;
+            x
+        }
+        StreamCell::Nil => unreachable!(),
+    }
+}
+
+/// stream of the elements of `xs` in order
+pub(super) fn stream<T: Clone + 'static>(xs: impl DoubleEndedIterator<Item = T>) -> Stream<T> {
+    xs.rev()
+        .fold(Stream::empty(), |s, x| Stream::cons(lazy!((x, s))))
+}
+
+/// move all but the first `k` of the `n` elements of `from` to the empty `to`, reversed
+fn split<T: Clone + 'static>(from: &mut Stream<T>, n: usize, k: usize, to: &mut Stream<T>) {
+    let mut xs = from.iter().take(n).collect::<Vec<_>>();
+    let rest = xs.split_off(k);
+    *to = stream(rest.into_iter().rev());
+    *from = stream(xs.into_iter());
+}
+
+impl<T: Clone + 'static> Iterator for Iter<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<T> {
+        if self.lenf == 0 {
+            if self.lenr == 0 {
+                return None;
+            }
+            let k = self.lenr / 2;
+            split(&mut self.r, self.lenr, k, &mut self.f);
+            (self.lenf, self.lenr) = (self.lenr - k, k);
+        }
+        self.lenf -= 1;
+        Some(pop(&mut self.f))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.lenf + self.lenr, Some(self.lenf + self.lenr))
+    }
 }
 
-impl_deque!(BankersDeque);
+impl<T: Clone + 'static> DoubleEndedIterator for Iter<T> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.lenr == 0 {
+            if self.lenf == 0 {
+                return None;
+            }
+            let k = self.lenf / 2;
+            split(&mut self.f, self.lenf, k, &mut self.r);
+            (self.lenr, self.lenf) = (self.lenf - k, k);
+        }
+        self.lenr -= 1;
+        Some(pop(&mut self.r))
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,309 @@
+use std::ops::ControlFlow;
+
+use crate::{
+    rebuild::{Rebuild, Rebuilding, Replay},
+    List,
+};
+
+use super::impl_deque;
+
+/// Real-time deque by global rebuilding (section 8.1) with worst-case O(1) operations.
+/// The working copy is a pair of lists kept within a factor `C` of each other; once an
+/// operation breaks the balance, a balanced copy is built `STEPS` steps per operation
+/// while the working copy serves the operations, which are replayed on the new copy.
+#[derive(Clone)]
+pub struct GlobalRebuildingDeque<T: Clone> {
+    work: Halves<T>,
+    state: Rebuilding<Split<T>, Update<T>>,
+}
+
+const C: usize = 2;
+/// Steps of rebuilding per operation. The operation unbalancing the working copy leaves
+/// some `m` elements in the shorter list and `n = (C + 1) m + 2` in all. The split takes
+/// `m + n + n / 2 + 3 <= 5.5 m + 6` steps and the replay one more per operation meanwhile,
+/// so at 16 steps per operation the fresh copy replaces the working one after
+/// `k <= (5.5 m + 5) / 15` more operations. That is before the shorter list runs out
+/// (`k < m`), and the fresh halves of about `n / 2` elements each, moved apart by at most
+/// `k <= m / 2` in the replay, are balanced again. The latter needs at least 12 steps.
+const STEPS: usize = 16;
+
+#[derive(Clone)]
+struct Halves<T> {
+    lenf: usize,
+    f: List<T>,
+    lenr: usize,
+    r: List<T>,
+}
+
+#[derive(Clone)]
+enum Update<T> {
+    Cons(T),
+    Snoc(T),
+    Tail,
+    Init,
+}
+
+/// the contents of `Halves` split evenly
+#[derive(Clone)]
+enum Split<T> {
+    /// reversing the rear list
+    Reversing {
+        f: List<T>,
+        r: List<T>,
+        rr: List<T>,
+        n: usize,
+    },
+    /// walking the contents, collecting the first half reversed and the rest as the rear
+    Splitting {
+        f: List<T>,
+        rr: List<T>,
+        k: usize,
+        n: usize,
+        ff: List<T>,
+        r: List<T>,
+    },
+    /// reversing the first half into the front
+    Reversed {
+        ff: List<T>,
+        f: List<T>,
+        n: usize,
+        r: List<T>,
+    },
+}
+
+impl<T: Clone> Halves<T> {
+    fn empty() -> Self {
+        Self {
+            lenf: 0,
+            f: List::empty(),
+            lenr: 0,
+            r: List::empty(),
+        }
+    }
+    fn is_balanced(&self) -> bool {
+        self.lenf <= C * self.lenr + 1 && self.lenr <= C * self.lenf + 1
+    }
+    fn cons(&self, x: T) -> Self {
+        Self {
+            lenf: self.lenf + 1,
+            f: List::cons(x, self.f.clone()),
+            lenr: self.lenr,
+            r: self.r.clone(),
+        }
+    }
+    fn snoc(&self, x: T) -> Self {
+        Self {
+            lenf: self.lenf,
+            f: self.f.clone(),
+            lenr: self.lenr + 1,
+            r: List::cons(x, self.r.clone()),
+        }
+    }
+    fn head(&self) -> Option<T> {
+        self.f.head().or_else(|| self.r.head())
+    }
+    fn last(&self) -> Option<T> {
+        self.r.head().or_else(|| self.f.head())
+    }
+    /// the front list is empty only if the rear has at most one element
+    fn tail(&self) -> Option<Self> {
+        match self.f.tail() {
+            Some(f) => Some(Self {
+                lenf: self.lenf - 1,
+                f,
+                lenr: self.lenr,
+                r: self.r.clone(),
+            }),
+            None => match self.lenr {
+                0 => None,
+                1 => Some(Self::empty()),
+                _ => unreachable!("the front list ran out before the rebuilding finished"),
+            },
+        }
+    }
+    /// the rear list is empty only if the front has at most one element
+    fn init(&self) -> Option<Self> {
+        match self.r.tail() {
+            Some(r) => Some(Self {
+                lenf: self.lenf,
+                f: self.f.clone(),
+                lenr: self.lenr - 1,
+                r,
+            }),
+            None => match self.lenf {
+                0 => None,
+                1 => Some(Self::empty()),
+                _ => unreachable!("the rear list ran out before the rebuilding finished"),
+            },
+        }
+    }
+}
+
+impl<T: Clone> Replay<Update<T>> for Halves<T> {
+    fn replay(self, u: &Update<T>) -> Self {
+        match u {
+            Update::Cons(x) => self.cons(x.clone()),
+            Update::Snoc(x) => self.snoc(x.clone()),
+            Update::Tail => self.tail().unwrap(),
+            Update::Init => self.init().unwrap(),
+        }
+    }
+}
+
+impl<T: Clone> Split<T> {
+    fn new(xs: &Halves<T>) -> Self {
+        Split::Reversing {
+            f: xs.f.clone(),
+            r: xs.r.clone(),
+            rr: List::empty(),
+            n: xs.lenf + xs.lenr,
+        }
+    }
+}
+
+impl<T: Clone> Rebuild for Split<T> {
+    type Output = Halves<T>;
+    fn step(self) -> ControlFlow<Halves<T>, Self> {
+        ControlFlow::Continue(match self {
+            Split::Reversing { f, r, rr, n } => match r.head() {
+                Some(x) => Split::Reversing {
+                    f,
+                    r: r.tail().unwrap(),
+                    rr: List::cons(x, rr),
+                    n,
+                },
+                None => Split::Splitting {
+                    f,
+                    rr,
+                    k: 0,
+                    n,
+                    ff: List::empty(),
+                    r: List::empty(),
+                },
+            },
+            Split::Splitting { f, rr, k, n, ff, r } => {
+                let (x, f, rr) = match (f.head(), rr.head()) {
+                    (Some(x), _) => (x, f.tail().unwrap(), rr),
+                    (None, Some(x)) => (x, f, rr.tail().unwrap()),
+                    (None, None) => {
+                        return ControlFlow::Continue(Split::Reversed {
+                            ff,
+                            f: List::empty(),
+                            n,
+                            r,
+                        })
+                    }
+                };
+                let (ff, r) = if k < n / 2 {
+                    (List::cons(x, ff), r)
+                } else {
+                    (ff, List::cons(x, r))
+                };
+                Split::Splitting {
+                    f,
+                    rr,
+                    k: k + 1,
+                    n,
+                    ff,
+                    r,
+                }
+            }
+            Split::Reversed { ff, f, n, r } => match ff.head() {
+                Some(x) => Split::Reversed {
+                    ff: ff.tail().unwrap(),
+                    f: List::cons(x, f),
+                    n,
+                    r,
+                },
+                None => {
+                    return ControlFlow::Break(Halves {
+                        lenf: n / 2,
+                        f,
+                        lenr: n - n / 2,
+                        r,
+                    })
+                }
+            },
+        })
+    }
+}
+
+impl<T: Clone + 'static> GlobalRebuildingDeque<T> {
+    pub fn empty() -> Self {
+        Self {
+            work: Halves::empty(),
+            state: Rebuilding::Idle,
+        }
+    }
+    pub fn is_empty(&self) -> bool {
+        self.work.lenf + self.work.lenr == 0
+    }
+    /// O(1)
+    pub fn len(&self) -> usize {
+        self.work.lenf + self.work.lenr
+    }
+    /// record `u` applied to the working copy `work`, advance the rebuilding and start a
+    /// new one if `work` is out of balance
+    fn update(&self, work: Halves<T>, u: Update<T>) -> Self {
+        let state = self.state.clone().record(u).exec(STEPS);
+        let (work, state) = match state {
+            Rebuilding::Done(work) => (work, Rebuilding::Idle),
+            state => (work, state),
+        };
+        if !state.is_idle() || work.is_balanced() {
+            return Self { work, state };
+        }
+        match Rebuilding::start(Split::new(&work)).exec(STEPS) {
+            Rebuilding::Done(work) => Self {
+                work,
+                state: Rebuilding::Idle,
+            },
+            state => Self { work, state },
+        }
+    }
+    pub fn cons(x: T, xs: &Self) -> Self {
+        xs.update(xs.work.cons(x.clone()), Update::Cons(x))
+    }
+    pub fn head(&self) -> Option<T> {
+        self.work.head()
+    }
+    pub fn tail(&self) -> Option<Self> {
+        Some(self.update(self.work.tail()?, Update::Tail))
+    }
+    pub fn snoc(&self, x: T) -> Self {
+        self.update(self.work.snoc(x.clone()), Update::Snoc(x))
+    }
+    pub fn last(&self) -> Option<T> {
+        self.work.last()
+    }
+    pub fn init(&self) -> Option<Self> {
+        Some(self.update(self.work.init()?, Update::Init))
+    }
+}
+
+impl_deque!(GlobalRebuildingDeque, {
+    fn len(&self) -> usize {
+        GlobalRebuildingDeque::len(self)
+    }
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test() {
+        let d = (0..10).fold(GlobalRebuildingDeque::empty(), |d, x| d.snoc(x));
+        let mut d = (10..20).fold(d, |d, x| GlobalRebuildingDeque::cons(x, &d));
+        for x in 0..10 {
+            assert_eq!(d.last(), Some(9 - x));
+            d = d.init().unwrap();
+        }
+        for x in 0..10 {
+            assert_eq!(d.last(), Some(10 + x));
+            d = d.init().unwrap();
+        }
+        assert!(d.is_empty());
+        assert!(d.init().is_none());
+    }
+}
@@ -0,0 +1,289 @@
+use std::sync::Arc;
+
+use crate::{lazy, lazy::Lazy};
+
+use super::{bankers_deque::BankersDeque, impl_deque, CatenableDeque};
+
+/// Catenable deque by implicit recursive slowdown (section 11.2) with O(1) amortized
+/// operations. Simple and compound elements of the nested deques share the erased
+/// `Elem<T>` with the leaves instead of using polymorphic recursion.
+#[derive(Clone)]
+pub struct ImplicitCatenableDeque<T>(Arc<Node<T>>);
+
+#[derive(Clone)]
+enum Elem<T> {
+    Leaf(T),
+    Simple(D<T>),
+    Cmpd(D<T>, Lazy<ImplicitCatenableDeque<T>>, D<T>),
+}
+
+type D<T> = BankersDeque<Elem<T>>;
+type Cat<T> = Lazy<ImplicitCatenableDeque<T>>;
+
+enum Node<T> {
+    Shallow(D<T>),
+    /// front and rear deques have at least three elements, the middle one at least two
+    Deep(D<T>, Cat<T>, D<T>, Cat<T>, D<T>),
+}
+
+impl<T> From<Node<T>> for ImplicitCatenableDeque<T> {
+    fn from(x: Node<T>) -> Self {
+        Self(Arc::new(x))
+    }
+}
+
+fn leaf<T>(x: Elem<T>) -> T {
+    match x {
+        Elem::Leaf(x) => x,
+        _ => unreachable!(),
+    }
+}
+
+fn share<T: Clone + 'static>(f: &D<T>, r: &D<T>) -> (D<T>, D<T>, D<T>) {
+    let m = BankersDeque::empty()
+        .snoc(f.last().unwrap())
+        .snoc(r.head().unwrap());
+    (f.init().unwrap(), m, r.tail().unwrap())
+}
+
+fn dappend_l<T: Clone + 'static>(mut d1: D<T>, mut d2: D<T>) -> D<T> {
+    while let Some(x) = d1.last() {
+        d2 = BankersDeque::cons(x, &d2);
+        d1 = d1.init().unwrap();
+    }
+    d2
+}
+
+fn dappend_r<T: Clone + 'static>(mut d1: D<T>, mut d2: D<T>) -> D<T> {
+    while let Some(x) = d2.head() {
+        d1 = d1.snoc(x);
+        d2 = d2.tail().unwrap();
+    }
+    d1
+}
+
+impl<T: Clone + 'static> ImplicitCatenableDeque<T> {
+    pub fn empty() -> Self {
+        Node::Shallow(BankersDeque::empty()).into()
+    }
+    pub fn is_empty(&self) -> bool {
+        matches!(self.0.as_ref(), Node::Shallow(d) if d.is_empty())
+    }
+    pub fn cons(x: T, xs: &Self) -> Self {
+        xs.cons_elem(Elem::Leaf(x))
+    }
+    pub fn head(&self) -> Option<T> {
+        self.head_elem().map(leaf)
+    }
+    pub fn tail(&self) -> Option<Self> {
+        let (f, a, m, b, r) = match self.0.as_ref() {
+            Node::Shallow(d) => return Some(Node::Shallow(d.tail()?).into()),
+            Node::Deep(f, a, m, b, r) => (f, a, m, b, r),
+        };
+        let ft = f.tail().unwrap();
+        if f.len() > 3 {
+            return Some(Node::Deep(ft, a.clone(), m.clone(), b.clone(), r.clone()).into());
+        }
+        let (fa, fb) = (a.force(), b.force());
+        Some(match (fa.head_elem(), fb.head_elem()) {
+            (Some(Elem::Simple(d)), _) => Node::Deep(
+                dappend_l(ft, d),
+                lazy!(fa.tail().unwrap()),
+                m.clone(),
+                b.clone(),
+                r.clone(),
+            )
+            .into(),
+            (Some(Elem::Cmpd(f1, c1, r1)), _) => {
+                let a = lazy!(c1
+                    .force()
+                    .append(&fa.replace_head(Elem::Simple(r1.clone()))));
+                Node::Deep(dappend_l(ft, f1), a, m.clone(), b.clone(), r.clone()).into()
+            }
+            (None, Some(Elem::Simple(d))) => Node::Deep(
+                dappend_l(ft, m.clone()),
+                lazy!(Self::empty()),
+                d,
+                lazy!(fb.tail().unwrap()),
+                r.clone(),
+            )
+            .into(),
+            (None, Some(Elem::Cmpd(f1, c1, r1))) => Node::Deep(
+                dappend_l(ft, m.clone()),
+                lazy!(c1.force().cons_elem(Elem::Simple(f1.clone()))),
+                r1,
+                lazy!(fb.tail().unwrap()),
+                r.clone(),
+            )
+            .into(),
+            (None, None) => Self::from(Node::Shallow(dappend_l(ft, m.clone())))
+                .append(&Node::Shallow(r.clone()).into()),
+            _ => unreachable!(),
+        })
+    }
+    pub fn snoc(&self, x: T) -> Self {
+        self.snoc_elem(Elem::Leaf(x))
+    }
+    pub fn last(&self) -> Option<T> {
+        self.last_elem().map(leaf)
+    }
+    pub fn init(&self) -> Option<Self> {
+        let (f, a, m, b, r) = match self.0.as_ref() {
+            Node::Shallow(d) => return Some(Node::Shallow(d.init()?).into()),
+            Node::Deep(f, a, m, b, r) => (f, a, m, b, r),
+        };
+        let ri = r.init().unwrap();
+        if r.len() > 3 {
+            return Some(Node::Deep(f.clone(), a.clone(), m.clone(), b.clone(), ri).into());
+        }
+        let (fa, fb) = (a.force(), b.force());
+        Some(match (fa.last_elem(), fb.last_elem()) {
+            (_, Some(Elem::Simple(d))) => Node::Deep(
+                f.clone(),
+                a.clone(),
+                m.clone(),
+                lazy!(fb.init().unwrap()),
+                dappend_r(d, ri),
+            )
+            .into(),
+            (_, Some(Elem::Cmpd(f1, c1, r1))) => {
+                let b = lazy!(fb
+                    .replace_last(Elem::Simple(f1.clone()))
+                    .append(&c1.force()));
+                Node::Deep(f.clone(), a.clone(), m.clone(), b, dappend_r(r1, ri)).into()
+            }
+            (Some(Elem::Simple(d)), None) => Node::Deep(
+                f.clone(),
+                lazy!(fa.init().unwrap()),
+                d,
+                lazy!(Self::empty()),
+                dappend_r(m.clone(), ri),
+            )
+            .into(),
+            (Some(Elem::Cmpd(f1, c1, r1)), None) => Node::Deep(
+                f.clone(),
+                lazy!(fa.init().unwrap()),
+                f1,
+                lazy!(c1.force().snoc_elem(Elem::Simple(r1.clone()))),
+                dappend_r(m.clone(), ri),
+            )
+            .into(),
+            (None, None) => Self::from(Node::Shallow(f.clone()))
+                .append(&Node::Shallow(dappend_r(m.clone(), ri)).into()),
+            _ => unreachable!(),
+        })
+    }
+    /// O(1) amortized
+    pub fn append(&self, other: &Self) -> Self {
+        match (self.0.as_ref(), other.0.as_ref()) {
+            (Node::Shallow(d1), Node::Shallow(d2)) => {
+                if d1.len() < 4 {
+                    Node::Shallow(dappend_l(d1.clone(), d2.clone())).into()
+                } else if d2.len() < 4 {
+                    Node::Shallow(dappend_r(d1.clone(), d2.clone())).into()
+                } else {
+                    let (f, m, r) = share(d1, d2);
+                    Node::Deep(f, lazy!(Self::empty()), m, lazy!(Self::empty()), r).into()
+                }
+            }
+            (Node::Shallow(d), Node::Deep(f, a, m, b, r)) => {
+                if d.len() < 4 {
+                    let f = dappend_l(d.clone(), f.clone());
+                    Node::Deep(f, a.clone(), m.clone(), b.clone(), r.clone()).into()
+                } else {
+                    let (f, a) = (f.clone(), a.clone());
+                    let a = lazy!(a.force().cons_elem(Elem::Simple(f)));
+                    Node::Deep(d.clone(), a, m.clone(), b.clone(), r.clone()).into()
+                }
+            }
+            (Node::Deep(f, a, m, b, r), Node::Shallow(d)) => {
+                if d.len() < 4 {
+                    let r = dappend_r(r.clone(), d.clone());
+                    Node::Deep(f.clone(), a.clone(), m.clone(), b.clone(), r).into()
+                } else {
+                    let (b, r) = (b.clone(), r.clone());
+                    let b = lazy!(b.force().snoc_elem(Elem::Simple(r)));
+                    Node::Deep(f.clone(), a.clone(), m.clone(), b, d.clone()).into()
+                }
+            }
+            (Node::Deep(f1, a1, m1, b1, r1), Node::Deep(f2, a2, m2, b2, r2)) => {
+                let (r1, m, f2) = share(r1, f2);
+                let (a1, m1, b1) = (a1.clone(), m1.clone(), b1.clone());
+                let a = lazy!(a1.force().snoc_elem(Elem::Cmpd(m1, b1, r1)));
+                let (a2, m2, b2) = (a2.clone(), m2.clone(), b2.clone());
+                let b = lazy!(b2.force().cons_elem(Elem::Cmpd(f2, a2, m2)));
+                Node::Deep(f1.clone(), a, m, b, r2.clone()).into()
+            }
+        }
+    }
+    fn cons_elem(&self, x: Elem<T>) -> Self {
+        match self.0.as_ref() {
+            Node::Shallow(d) => Node::Shallow(BankersDeque::cons(x, d)).into(),
+            Node::Deep(f, a, m, b, r) => {
+                let f = BankersDeque::cons(x, f);
+                Node::Deep(f, a.clone(), m.clone(), b.clone(), r.clone()).into()
+            }
+        }
+    }
+    fn snoc_elem(&self, x: Elem<T>) -> Self {
+        match self.0.as_ref() {
+            Node::Shallow(d) => Node::Shallow(d.snoc(x)).into(),
+            Node::Deep(f, a, m, b, r) => {
+                Node::Deep(f.clone(), a.clone(), m.clone(), b.clone(), r.snoc(x)).into()
+            }
+        }
+    }
+    fn head_elem(&self) -> Option<Elem<T>> {
+        match self.0.as_ref() {
+            Node::Shallow(d) => d.head(),
+            Node::Deep(f, ..) => f.head(),
+        }
+    }
+    fn last_elem(&self) -> Option<Elem<T>> {
+        match self.0.as_ref() {
+            Node::Shallow(d) => d.last(),
+            Node::Deep(.., r) => r.last(),
+        }
+    }
+    fn replace_head(&self, x: Elem<T>) -> Self {
+        self.tail().unwrap().cons_elem(x)
+    }
+    fn replace_last(&self, x: Elem<T>) -> Self {
+        self.init().unwrap().snoc_elem(x)
+    }
+}
+
+impl_deque!(ImplicitCatenableDeque, {
+    fn append(&self, other: &Self) -> Self {
+        CatenableDeque::append(self, other)
+    }
+});
+
+impl<T: Clone + 'static> CatenableDeque<T> for ImplicitCatenableDeque<T> {
+    fn append(&self, other: &Self) -> Self {
+        ImplicitCatenableDeque::append(self, other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deque::Deque;
+
+    #[test]
+    fn test() {
+        let d1 = (0..5).fold(ImplicitCatenableDeque::empty(), |d, x| d.snoc(x));
+        let d2 = (5..10).rev().fold(ImplicitCatenableDeque::empty(), |d, x| {
+            ImplicitCatenableDeque::cons(x, &d)
+        });
+        let d = d1.append(&d2).append(&d1);
+        assert_eq!(d.head(), Some(0));
+        assert_eq!(d.last(), Some(4));
+        assert_eq!(
+            d.iter().collect::<Vec<_>>(),
+            [(0..10).collect::<Vec<_>>(), (0..5).collect()].concat()
+        );
+        assert_eq!(d.init().unwrap().tail().unwrap().len(), 13);
+        assert!(ImplicitCatenableDeque::<i32>::empty().init().is_none());
+    }
+}
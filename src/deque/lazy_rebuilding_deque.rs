@@ -0,0 +1,268 @@
+use crate::{lazy, lazy::Lazy, List};
+
+use super::impl_deque;
+
+/// Deque by lazy rebuilding (section 8.3) in the style of `PhysicistsQueue`. Both lists are
+/// monolithic suspensions rebalanced with the same `C` as `BankersDeque`, and evaluated
+/// working prefixes answer `head` and `last`. Only the first `lenwf` elements of `wf` are
+/// known to be a prefix of the front, so a rebuild shortening the front keeps `wf` as is.
+///
+/// Suspensions are created only by rebuilds. In between, `cons` and `tail` touch just the
+/// working prefix, which always covers the elements consed since, and the rest of the front
+/// is the last `lenf - lenwf` elements of the `lensf` in `f`. A rebuild forces the
+/// suspensions of the previous one, paid for by the operations in between, so forcing
+/// never goes through a chain of suspensions.
+#[derive(Clone)]
+pub struct LazyRebuildingDequeC<T, const C: usize> {
+    lenwf: usize,
+    wf: List<T>,
+    lenf: usize,
+    lensf: usize,
+    f: Lazy<List<T>>,
+    lenwr: usize,
+    wr: List<T>,
+    lenr: usize,
+    lensr: usize,
+    r: Lazy<List<T>>,
+}
+
+/// `LazyRebuildingDequeC` with the balance constant 2 of the book
+pub type LazyRebuildingDeque<T> = LazyRebuildingDequeC<T, 2>;
+
+fn from_vec<T: Clone>(xs: Vec<T>) -> List<T> {
+    xs.into_iter()
+        .rev()
+        .fold(List::empty(), |xs, x| List::cons(x, xs))
+}
+
+/// the first `k` elements of `xs` followed by those of `ys` after the first `l` in reverse
+fn rebuilt<T: Clone>(mut xs: Vec<T>, k: usize, ys: Vec<T>, l: usize) -> List<T> {
+    xs.truncate(k);
+    xs.extend(ys.into_iter().skip(l).rev());
+    from_vec(xs)
+}
+
+/// `xs` without its first `n` elements
+fn skip<T: Clone>(xs: &List<T>, n: usize) -> List<T> {
+    (0..n).fold(xs.clone(), |xs, _| xs.tail().unwrap())
+}
+
+impl<T: Clone + 'static, const C: usize> LazyRebuildingDequeC<T, C> {
+    /// `C`, rejected at compile time unless at least 2
+    const BALANCE: usize = {
+        assert!(
+            C >= 2,
+            "the balance constant of LazyRebuildingDeque must be at least 2"
+        );
+        C
+    };
+    pub fn empty() -> Self {
+        Self {
+            lenwf: 0,
+            wf: List::empty(),
+            lenf: 0,
+            lensf: 0,
+            f: Lazy::value(List::empty()),
+            lenwr: 0,
+            wr: List::empty(),
+            lenr: 0,
+            lensr: 0,
+            r: Lazy::value(List::empty()),
+        }
+    }
+    pub fn is_empty(&self) -> bool {
+        self.lenf + self.lenr == 0
+    }
+    /// O(1)
+    pub fn len(&self) -> usize {
+        self.lenf + self.lenr
+    }
+    /// elements of the front and of the rear, each starting from its end of the deque
+    fn contents(&self) -> (Vec<T>, Vec<T>) {
+        let f = self.f.force();
+        let r = self.r.force();
+        let f = self
+            .wf
+            .iter()
+            .take(self.lenwf)
+            .chain(f.iter().skip(self.lensf + self.lenwf - self.lenf));
+        let r = self
+            .wr
+            .iter()
+            .take(self.lenwr)
+            .chain(r.iter().skip(self.lensr + self.lenwr - self.lenr));
+        (f.cloned().collect(), r.cloned().collect())
+    }
+    /// refill the working prefixes once exhausted, when nothing has been consed since the
+    /// last rebuild
+    fn checkw(self) -> Self {
+        let (lenwf, wf) = if self.lenwf == 0 {
+            (self.lenf, skip(&self.f.force(), self.lensf - self.lenf))
+        } else {
+            (self.lenwf, self.wf)
+        };
+        let (lenwr, wr) = if self.lenwr == 0 {
+            (self.lenr, skip(&self.r.force(), self.lensr - self.lenr))
+        } else {
+            (self.lenwr, self.wr)
+        };
+        Self {
+            lenwf,
+            wf,
+            lenwr,
+            wr,
+            ..self
+        }
+    }
+    fn check(self) -> Self {
+        let c = Self::BALANCE;
+        let n = self.lenf + self.lenr;
+        if self.lenf > c * self.lenr + 1 || self.lenr > c * self.lenf + 1 {
+            let (i, j) = if self.lenf > self.lenr {
+                (n / 2, n - n / 2)
+            } else {
+                (n - n / 2, n / 2)
+            };
+            // the longer side keeps its first half and gives the rest to the other reversed
+            let this = Self {
+                f: Lazy::value(self.f.force()),
+                r: Lazy::value(self.r.force()),
+                ..self.clone()
+            };
+            let that = this.clone();
+            Self {
+                lenwf: self.lenwf.min(i),
+                lenf: i,
+                lensf: i,
+                f: lazy!({
+                    let (f, r) = this.contents();
+                    rebuilt(f, i, r, j)
+                }),
+                lenwr: self.lenwr.min(j),
+                lenr: j,
+                lensr: j,
+                r: lazy!({
+                    let (f, r) = that.contents();
+                    rebuilt(r, j, f, i)
+                }),
+                ..self
+            }
+        } else {
+            self
+        }
+        .checkw()
+    }
+    pub fn cons(x: T, xs: &Self) -> Self {
+        Self {
+            lenwf: xs.lenwf + 1,
+            wf: List::cons(x, xs.wf.clone()),
+            lenf: xs.lenf + 1,
+            ..xs.clone()
+        }
+        .check()
+    }
+    pub fn head(&self) -> Option<T> {
+        if self.lenwf > 0 {
+            self.wf.head()
+        } else if self.lenwr > 0 {
+            self.wr.head()
+        } else {
+            None
+        }
+    }
+    /// the front is empty only if the rear has at most one element
+    pub fn tail(&self) -> Option<Self> {
+        if self.lenf == 0 {
+            return (self.lenr > 0).then(Self::empty);
+        }
+        Some(
+            Self {
+                lenwf: self.lenwf - 1,
+                wf: self.wf.tail().unwrap(),
+                lenf: self.lenf - 1,
+                ..self.clone()
+            }
+            .check(),
+        )
+    }
+    pub fn snoc(&self, x: T) -> Self {
+        Self {
+            lenwr: self.lenwr + 1,
+            wr: List::cons(x, self.wr.clone()),
+            lenr: self.lenr + 1,
+            ..self.clone()
+        }
+        .check()
+    }
+    pub fn last(&self) -> Option<T> {
+        if self.lenwr > 0 {
+            self.wr.head()
+        } else if self.lenwf > 0 {
+            self.wf.head()
+        } else {
+            None
+        }
+    }
+    /// the rear is empty only if the front has at most one element
+    pub fn init(&self) -> Option<Self> {
+        if self.lenr == 0 {
+            return (self.lenf > 0).then(Self::empty);
+        }
+        Some(
+            Self {
+                lenwr: self.lenwr - 1,
+                wr: self.wr.tail().unwrap(),
+                lenr: self.lenr - 1,
+                ..self.clone()
+            }
+            .check(),
+        )
+    }
+}
+
+impl_deque!(LazyRebuildingDequeC<const C>, {
+    fn len(&self) -> usize {
+        LazyRebuildingDequeC::len(self)
+    }
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test() {
+        let d = (0..10).fold(LazyRebuildingDeque::empty(), |d, x| d.snoc(x));
+        let mut d = (10..20).fold(d, |d, x| LazyRebuildingDeque::cons(x, &d));
+        for x in 0..10 {
+            assert_eq!(d.last(), Some(9 - x));
+            d = d.init().unwrap();
+        }
+        for x in 0..10 {
+            assert_eq!(d.last(), Some(10 + x));
+            d = d.init().unwrap();
+        }
+        assert!(d.is_empty());
+        assert!(d.init().is_none());
+    }
+
+    #[test]
+    fn test_large() {
+        // long runs of operations between rebuilds must not nest suspensions
+        let n = 100_000;
+        let mut d = (0..n).fold(LazyRebuildingDeque::empty(), |d, x| d.snoc(x));
+        for x in 0..n {
+            assert_eq!(d.head(), Some(x));
+            d = d.tail().unwrap();
+        }
+        assert!(d.is_empty());
+        let mut d = (0..n).fold(LazyRebuildingDeque::empty(), |d, x| {
+            LazyRebuildingDeque::cons(x, &d)
+        });
+        for x in 0..n {
+            assert_eq!(d.last(), Some(x));
+            d = d.init().unwrap();
+        }
+        assert!(d.is_empty());
+    }
+}
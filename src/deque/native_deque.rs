@@ -2,93 +2,126 @@ use crate::List;
 
 use super::impl_deque;
 
+/// Deque of a front list and a reversed rear list (Exercise 5.1). Both lists are kept
+/// non-empty whenever the deque has two or more elements by splitting the other list in
+/// half once one of them runs out.
+///
+/// With the potential `2 |lenf - lenr|`, `cons`, `snoc` and a `tail` or `init` without a
+/// split change it by at most 2. A split of `m` elements costs O(m) but starts from a
+/// potential of about `2m` and leaves at most 2, so every operation is amortized O(1).
 #[derive(Clone)]
-pub struct NaiveDeque<T>(List<T>, List<T>, usize, usize);
+pub struct NaiveDeque<T> {
+    lenf: usize,
+    f: List<T>,
+    lenr: usize,
+    r: List<T>,
+}
 
 impl<T: Clone> NaiveDeque<T> {
     pub fn empty() -> Self {
-        let e = List::empty();
-        Self(e.clone(), e, 0, 0)
+        Self {
+            lenf: 0,
+            f: List::empty(),
+            lenr: 0,
+            r: List::empty(),
+        }
     }
-
     pub fn is_empty(&self) -> bool {
-        self.0.is_empty()
+        self.lenf + self.lenr == 0
+    }
+    /// O(1)
+    pub fn len(&self) -> usize {
+        self.lenf + self.lenr
     }
-
     fn check(self) -> Self {
-        if self.2 == 0 {
-            Self(
-                self.1.drop(self.3 / 2).reverse(),
-                self.1.take(self.3 / 2),
-                (self.3 + 1) / 2,
-                self.3 / 2,
-            )
-        } else if self.3 == 0 {
-            Self(
-                self.0.take(self.2 / 2),
-                self.0.drop(self.2 / 2).reverse(),
-                self.2 / 2,
-                (self.2 + 1) / 2,
-            )
-        } else {
+        let n = self.lenf + self.lenr;
+        if n < 2 || (self.lenf > 0 && self.lenr > 0) {
             self
+        } else if self.lenf == 0 {
+            let j = n / 2;
+            Self {
+                lenf: n - j,
+                f: self.r.drop(j).reverse(),
+                lenr: j,
+                r: self.r.take(j),
+            }
+        } else {
+            let i = n / 2;
+            Self {
+                lenf: i,
+                f: self.f.take(i),
+                lenr: n - i,
+                r: self.f.drop(i).reverse(),
+            }
         }
     }
-
-    pub fn cons(head: T, tail: &Self) -> Self {
-        Self(
-            List::cons(head, tail.0.clone()),
-            tail.1.clone(),
-            tail.2 + 1,
-            tail.3,
-        )
+    pub fn cons(x: T, xs: &Self) -> Self {
+        Self {
+            lenf: xs.lenf + 1,
+            f: List::cons(x, xs.f.clone()),
+            lenr: xs.lenr,
+            r: xs.r.clone(),
+        }
         .check()
     }
-
     pub fn head(&self) -> Option<T> {
-        if self.0.is_empty() {
-            self.1.head()
-        } else {
-            self.0.head()
-        }
+        self.f.head().or_else(|| self.r.head())
     }
-
+    /// the front list is empty only if the rear has at most one element
     pub fn tail(&self) -> Option<Self> {
-        Some(Self(self.0.clone().tail()?, self.1.clone(), self.2 - 1, self.3).check())
+        match self.f.tail() {
+            Some(f) => Some(
+                Self {
+                    lenf: self.lenf - 1,
+                    f,
+                    lenr: self.lenr,
+                    r: self.r.clone(),
+                }
+                .check(),
+            ),
+            None => self.r.tail().map(|_| Self::empty()),
+        }
     }
-
-    pub fn snoc(init: &Self, last: T) -> Self {
-        Self(
-            init.0.clone(),
-            List::cons(last, init.1.clone()),
-            init.2,
-            init.3 + 1,
-        )
+    pub fn snoc(&self, x: T) -> Self {
+        Self {
+            lenf: self.lenf,
+            f: self.f.clone(),
+            lenr: self.lenr + 1,
+            r: List::cons(x, self.r.clone()),
+        }
         .check()
     }
-
     pub fn last(&self) -> Option<T> {
-        if self.1.is_empty() {
-            self.0.head()
-        } else {
-            self.1.head()
-        }
+        self.r.head().or_else(|| self.f.head())
     }
-
+    /// the rear list is empty only if the front has at most one element
     pub fn init(&self) -> Option<Self> {
-        if self.1.is_empty() {
-            self.tail()
-        } else {
-            Some(Self(self.0.clone(), self.1.tail()?, self.2, self.3 - 1).check())
+        match self.r.tail() {
+            Some(r) => Some(
+                Self {
+                    lenf: self.lenf,
+                    f: self.f.clone(),
+                    lenr: self.lenr - 1,
+                    r,
+                }
+                .check(),
+            ),
+            None => self.f.tail().map(|_| Self::empty()),
         }
     }
 }
 
-impl_deque!(NaiveDeque);
+impl_deque!(NaiveDeque, {
+    fn len(&self) -> usize {
+        NaiveDeque::len(self)
+    }
+});
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::prelude::*;
+    use std::collections::VecDeque;
 
     #[test]
     fn test() {
@@ -117,4 +150,75 @@ mod tests {
         assert_eq!(deq.head(), Some(4));
         assert_eq!(deq.last(), Some(5));
     }
+
+    #[test]
+    fn test_single() {
+        // a single element may sit in either list
+        for deq in [
+            NaiveDeque::cons(1, &NaiveDeque::empty()),
+            NaiveDeque::empty().snoc(1),
+            NaiveDeque::cons(0, &NaiveDeque::empty().snoc(1))
+                .tail()
+                .unwrap(),
+            NaiveDeque::cons(1, &NaiveDeque::empty().snoc(0))
+                .init()
+                .unwrap(),
+        ] {
+            assert!(!deq.is_empty());
+            assert_eq!(deq.len(), 1);
+            assert_eq!((deq.head(), deq.last()), (Some(1), Some(1)));
+            assert!(deq.tail().unwrap().is_empty());
+            assert!(deq.init().unwrap().is_empty());
+        }
+        assert!(NaiveDeque::<i32>::empty().tail().is_none());
+        assert!(NaiveDeque::<i32>::empty().init().is_none());
+    }
+
+    /// lengths agree with the lists, which are both non-empty from two elements on
+    fn check_invariant<T: Clone>(deq: &NaiveDeque<T>) {
+        assert_eq!(deq.f.size(), deq.lenf);
+        assert_eq!(deq.r.size(), deq.lenr);
+        assert!(deq.len() < 2 || (deq.lenf > 0 && deq.lenr > 0));
+    }
+
+    #[test]
+    fn test_against_vec() {
+        let mut rng = StdRng::seed_from_u64(50);
+        let mut pool = vec![(NaiveDeque::empty(), VecDeque::new())];
+        for x in 0..3000 {
+            let (mut d, mut v) = pool.choose(&mut rng).unwrap().clone();
+            match rng.gen_range(0..5) {
+                0 => {
+                    d = NaiveDeque::cons(x, &d);
+                    v.push_front(x);
+                }
+                1 => {
+                    d = d.snoc(x);
+                    v.push_back(x);
+                }
+                2 => {
+                    assert_eq!(d.tail().is_some(), v.pop_front().is_some());
+                    d = d.tail().unwrap_or(d);
+                }
+                3 => {
+                    assert_eq!(d.init().is_some(), v.pop_back().is_some());
+                    d = d.init().unwrap_or(d);
+                }
+                _ => {
+                    // drain the front list to force a split of the rear
+                    for _ in 0..d.lenf {
+                        d = d.tail().unwrap();
+                        v.pop_front();
+                        check_invariant(&d);
+                    }
+                }
+            }
+            check_invariant(&d);
+            assert_eq!(d.is_empty(), v.is_empty());
+            assert_eq!(d.len(), v.len());
+            assert_eq!(d.head(), v.front().copied());
+            assert_eq!(d.last(), v.back().copied());
+            pool.push((d, v));
+        }
+    }
 }
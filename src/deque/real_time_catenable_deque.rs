@@ -0,0 +1,721 @@
+use std::sync::Arc;
+
+use crate::{stack::Cell, List};
+
+use super::{impl_deque, real_time_deque::RealTimeDeque, CatenableDeque};
+
+/// Catenable deque by Kaplan and Tarjan's recursive slowdown with worst-case O(1)
+/// operations. A deque is one or two triples of real-time deques, and the child deque of
+/// a triple holds stored triples of the next level, which share the erased `Elem<T>` with
+/// the leaves. A chain of yellow and orange triples is kept as one packet so that the red
+/// triple at its end, the only one an operation may have to repair, is reachable in O(1).
+#[derive(Clone)]
+pub struct RealTimeCatenableDeque<T>(Arc<Chain<T>>);
+
+#[derive(Clone)]
+enum Elem<T> {
+    Leaf(T),
+    /// buffer of at least three elements
+    Small(D<T>),
+    /// buffers of at least three elements around a deque of the next level
+    Big(D<T>, RealTimeCatenableDeque<T>, D<T>),
+}
+
+type D<T> = RealTimeDeque<Elem<T>>;
+
+enum Chain<T> {
+    Empty,
+    /// preferred path from the top triple and the child deque of its last triple
+    Single(Packet<T>, RealTimeCatenableDeque<T>),
+    /// left and right triples, each as a `Single`
+    Pair(RealTimeCatenableDeque<T>, RealTimeCatenableDeque<T>),
+}
+
+/// yellow and orange triples each followed by its preferred child, up to a green or red
+/// triple
+#[derive(Clone)]
+struct Packet<T> {
+    body: List<Frame<T>>,
+    tail: Triple<T>,
+}
+
+#[derive(Clone)]
+enum Frame<T> {
+    /// the child deque has a single triple
+    Only(Triple<T>),
+    /// the path goes on to the left triple of the child deque, beside the right one
+    Left(Triple<T>, RealTimeCatenableDeque<T>),
+    /// the path goes on to the right triple of the child deque, beside the left one
+    Right(RealTimeCatenableDeque<T>, Triple<T>),
+}
+
+/// Buffers of a triple without its child. With a non-empty child, the buffers the triple
+/// is colored by have at least five elements, the suffix of a left triple and the prefix
+/// of a right triple exactly two. A left or right triple with an empty child has at least
+/// five elements on its outer side.
+#[derive(Clone)]
+struct Triple<T> {
+    kind: Kind,
+    pre: D<T>,
+    suf: D<T>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Only,
+    Left,
+    Right,
+}
+
+/// Each `tail` or `init` shrinks a buffer of a top triple by one and a repair shrinks
+/// those of the children by one, which costs a color. The preferred child of a yellow
+/// triple is its left one and that of an orange triple its right one. The path from the
+/// left child of an orange triple and those from the children of a red triple end green,
+/// and so do the paths from the top triples between operations.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+enum Color {
+    Red,
+    Orange,
+    Yellow,
+    Green,
+}
+
+impl Color {
+    fn of(n: usize) -> Self {
+        match n {
+            0..=5 => Color::Red,
+            6 => Color::Orange,
+            7 => Color::Yellow,
+            _ => Color::Green,
+        }
+    }
+}
+
+#[cfg(test)]
+thread_local! {
+    static STEPS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+/// count a primitive step for the worst-case tests
+fn step() {
+    #[cfg(test)]
+    STEPS.with(|s| s.set(s.get() + 1));
+}
+
+fn push<T: Clone + 'static>(x: Elem<T>, d: &D<T>) -> D<T> {
+    step();
+    RealTimeDeque::cons(x, d)
+}
+
+fn inject<T: Clone + 'static>(d: &D<T>, x: Elem<T>) -> D<T> {
+    step();
+    d.snoc(x)
+}
+
+fn pop<T: Clone + 'static>(d: &D<T>) -> Option<(Elem<T>, D<T>)> {
+    step();
+    Some((d.head()?, d.tail()?))
+}
+
+fn eject<T: Clone + 'static>(d: &D<T>) -> Option<(D<T>, Elem<T>)> {
+    step();
+    Some((d.init()?, d.last()?))
+}
+
+/// `d1 ++ d2` for a short `d1`
+fn prepend<T: Clone + 'static>(d1: &D<T>, d2: &D<T>) -> D<T> {
+    let (mut d1, mut d2) = (d1.clone(), d2.clone());
+    while let Some((d, x)) = eject(&d1) {
+        d2 = push(x, &d2);
+        d1 = d;
+    }
+    d2
+}
+
+/// `d1 ++ d2` for a short `d2`
+fn concat<T: Clone + 'static>(d1: &D<T>, d2: &D<T>) -> D<T> {
+    let (mut d1, mut d2) = (d1.clone(), d2.clone());
+    while let Some((x, d)) = pop(&d2) {
+        d1 = inject(&d1, x);
+        d2 = d;
+    }
+    d1
+}
+
+fn split_first2<T: Clone + 'static>(d: &D<T>) -> (D<T>, D<T>) {
+    let (x, d) = pop(d).unwrap();
+    let (y, d) = pop(&d).unwrap();
+    (push(x, &push(y, &D::empty())), d)
+}
+
+fn split_last2<T: Clone + 'static>(d: &D<T>) -> (D<T>, D<T>) {
+    let (d, y) = eject(d).unwrap();
+    let (d, x) = eject(&d).unwrap();
+    (d, inject(&inject(&D::empty(), x), y))
+}
+
+fn leaf<T>(x: Elem<T>) -> T {
+    match x {
+        Elem::Leaf(x) => x,
+        _ => unreachable!(),
+    }
+}
+
+impl<T: Clone + 'static> Triple<T> {
+    fn new(kind: Kind, pre: D<T>, suf: D<T>) -> Self {
+        Self { kind, pre, suf }
+    }
+    fn color(&self, child: &RealTimeCatenableDeque<T>) -> Color {
+        if child.is_empty() {
+            return Color::Green;
+        }
+        match self.kind {
+            Kind::Only => Color::of(self.pre.len()).min(Color::of(self.suf.len())),
+            Kind::Left => Color::of(self.pre.len()),
+            Kind::Right => Color::of(self.suf.len()),
+        }
+    }
+}
+
+impl<T> Frame<T> {
+    fn triple(&self) -> &Triple<T> {
+        match self {
+            Frame::Only(t) | Frame::Left(t, _) | Frame::Right(_, t) => t,
+        }
+    }
+}
+
+impl<T> From<Chain<T>> for RealTimeCatenableDeque<T> {
+    fn from(x: Chain<T>) -> Self {
+        Self(Arc::new(x))
+    }
+}
+
+impl<T: Clone + 'static> RealTimeCatenableDeque<T> {
+    pub fn empty() -> Self {
+        Chain::Empty.into()
+    }
+    pub fn is_empty(&self) -> bool {
+        matches!(self.0.as_ref(), Chain::Empty)
+    }
+    pub fn cons(x: T, xs: &Self) -> Self {
+        xs.push_elem(Elem::Leaf(x))
+    }
+    pub fn head(&self) -> Option<T> {
+        match self.0.as_ref() {
+            Chain::Empty => None,
+            Chain::Single(..) => {
+                let t = self.top();
+                t.pre.head().or_else(|| t.suf.head()).map(leaf)
+            }
+            Chain::Pair(l, _) => l.top().pre.head().map(leaf),
+        }
+    }
+    /// worst-case O(1)
+    pub fn tail(&self) -> Option<Self> {
+        Some(self.pop_elem()?.0.regularize())
+    }
+    pub fn snoc(&self, x: T) -> Self {
+        self.inject_elem(Elem::Leaf(x))
+    }
+    pub fn last(&self) -> Option<T> {
+        match self.0.as_ref() {
+            Chain::Empty => None,
+            Chain::Single(..) => {
+                let t = self.top();
+                t.suf.last().or_else(|| t.pre.last()).map(leaf)
+            }
+            Chain::Pair(_, r) => r.top().suf.last().map(leaf),
+        }
+    }
+    /// worst-case O(1)
+    pub fn init(&self) -> Option<Self> {
+        Some(self.eject_elem()?.0.regularize())
+    }
+    /// worst-case O(1)
+    pub fn append(&self, other: &Self) -> Self {
+        if self.is_empty() {
+            return other.clone();
+        }
+        if other.is_empty() {
+            return self.clone();
+        }
+        if let Some(xs) = self.short_elems() {
+            return xs
+                .into_iter()
+                .rev()
+                .fold(other.clone(), |d, x| d.push_elem(x));
+        }
+        if let Some(xs) = other.short_elems() {
+            return xs.into_iter().fold(self.clone(), |d, x| d.inject_elem(x));
+        }
+        let (l, lc) = self.left_triple();
+        let (r, rc) = other.right_triple();
+        Chain::Pair(Self::make(l, lc), Self::make(r, rc)).into()
+    }
+
+    /// `Single` of the triple `t` with the child deque `child`, merging the packet of the
+    /// preferred child into that of a yellow or orange triple
+    fn make(t: Triple<T>, child: Self) -> Self {
+        step();
+        let (frame, next) = match (t.color(&child), child.0.as_ref()) {
+            (Color::Green | Color::Red, _) => {
+                let p = Packet {
+                    body: List::empty(),
+                    tail: t,
+                };
+                return Chain::Single(p, child).into();
+            }
+            (_, Chain::Single(..)) => (Frame::Only(t), &child),
+            (Color::Yellow, Chain::Pair(l, r)) => (Frame::Left(t, r.clone()), l),
+            (Color::Orange, Chain::Pair(l, r)) => (Frame::Right(l.clone(), t), r),
+            (_, Chain::Empty) => unreachable!(),
+        };
+        match next.0.as_ref() {
+            Chain::Single(p, below) => {
+                let p = Packet {
+                    body: List::cons(frame, p.body.clone()),
+                    tail: p.tail.clone(),
+                };
+                Chain::Single(p, below.clone()).into()
+            }
+            _ => unreachable!(),
+        }
+    }
+    /// the top triple and its child deque of a `Single`
+    fn view(&self) -> (Triple<T>, Self) {
+        step();
+        let Chain::Single(p, below) = self.0.as_ref() else {
+            unreachable!()
+        };
+        let Cell::Cons(frame, body) = &*p.body else {
+            return (p.tail.clone(), below.clone());
+        };
+        let next = Self::from(Chain::Single(
+            Packet {
+                body: body.clone(),
+                tail: p.tail.clone(),
+            },
+            below.clone(),
+        ));
+        match frame {
+            Frame::Only(t) => (t.clone(), next),
+            Frame::Left(t, r) => (t.clone(), Chain::Pair(next, r.clone()).into()),
+            Frame::Right(l, t) => (t.clone(), Chain::Pair(l.clone(), next).into()),
+        }
+    }
+    /// the top triple of a `Single`
+    fn top(&self) -> &Triple<T> {
+        let Chain::Single(p, _) = self.0.as_ref() else {
+            unreachable!()
+        };
+        match &*p.body {
+            Cell::Cons(frame, _) => frame.triple(),
+            Cell::Nil => &p.tail,
+        }
+    }
+
+    fn push_elem(&self, x: Elem<T>) -> Self {
+        match self.0.as_ref() {
+            Chain::Empty => {
+                let t = Triple::new(Kind::Only, push(x, &D::empty()), D::empty());
+                Self::make(t, Self::empty())
+            }
+            Chain::Single(..) => {
+                let (t, c) = self.view();
+                Self::make(Triple::new(t.kind, push(x, &t.pre), t.suf), c)
+            }
+            Chain::Pair(l, r) => {
+                let (t, c) = l.view();
+                let l = Self::make(Triple::new(t.kind, push(x, &t.pre), t.suf), c);
+                Chain::Pair(l, r.clone()).into()
+            }
+        }
+    }
+    fn inject_elem(&self, x: Elem<T>) -> Self {
+        match self.0.as_ref() {
+            Chain::Empty => {
+                let t = Triple::new(Kind::Only, D::empty(), inject(&D::empty(), x));
+                Self::make(t, Self::empty())
+            }
+            Chain::Single(..) => {
+                let (t, c) = self.view();
+                Self::make(Triple::new(t.kind, t.pre, inject(&t.suf, x)), c)
+            }
+            Chain::Pair(l, r) => {
+                let (t, c) = r.view();
+                let r = Self::make(Triple::new(t.kind, t.pre, inject(&t.suf, x)), c);
+                Chain::Pair(l.clone(), r).into()
+            }
+        }
+    }
+    /// remove the first element without repairing the triple it came from
+    fn pop_elem(&self) -> Option<(Self, Elem<T>)> {
+        match self.0.as_ref() {
+            Chain::Empty => None,
+            Chain::Single(..) => {
+                let (t, c) = self.view();
+                let (x, t) = match pop(&t.pre) {
+                    Some((x, pre)) => (x, Triple::new(t.kind, pre, t.suf)),
+                    None => {
+                        let (x, suf) = pop(&t.suf).unwrap();
+                        (x, Triple::new(t.kind, t.pre, suf))
+                    }
+                };
+                if t.pre.is_empty() && t.suf.is_empty() {
+                    Some((Self::empty(), x))
+                } else {
+                    Some((Self::make(t, c), x))
+                }
+            }
+            Chain::Pair(l, r) => {
+                let (t, c) = l.view();
+                let (x, pre) = pop(&t.pre).unwrap();
+                if c.is_empty() && pre.len() < 5 {
+                    // merge the rest of the left triple into the right one
+                    let (u, rc) = r.view();
+                    let pre = prepend(&pre, &prepend(&t.suf, &u.pre));
+                    return Some((Self::make(Triple::new(Kind::Only, pre, u.suf), rc), x));
+                }
+                let l = Self::make(Triple::new(t.kind, pre, t.suf), c);
+                Some((Chain::Pair(l, r.clone()).into(), x))
+            }
+        }
+    }
+    /// remove the last element without repairing the triple it came from
+    fn eject_elem(&self) -> Option<(Self, Elem<T>)> {
+        match self.0.as_ref() {
+            Chain::Empty => None,
+            Chain::Single(..) => {
+                let (t, c) = self.view();
+                let (t, x) = match eject(&t.suf) {
+                    Some((suf, x)) => (Triple::new(t.kind, t.pre, suf), x),
+                    None => {
+                        let (pre, x) = eject(&t.pre).unwrap();
+                        (Triple::new(t.kind, pre, t.suf), x)
+                    }
+                };
+                if t.pre.is_empty() && t.suf.is_empty() {
+                    Some((Self::empty(), x))
+                } else {
+                    Some((Self::make(t, c), x))
+                }
+            }
+            Chain::Pair(l, r) => {
+                let (t, c) = r.view();
+                let (suf, x) = eject(&t.suf).unwrap();
+                if c.is_empty() && suf.len() < 5 {
+                    // merge the rest of the right triple into the left one
+                    let (u, lc) = l.view();
+                    let suf = concat(&concat(&u.suf, &t.pre), &suf);
+                    return Some((Self::make(Triple::new(Kind::Only, u.pre, suf), lc), x));
+                }
+                let r = Self::make(Triple::new(t.kind, t.pre, suf), c);
+                Some((Chain::Pair(l.clone(), r).into(), x))
+            }
+        }
+    }
+
+    /// repair the red triple at the end of each top path, if any
+    fn regularize(&self) -> Self {
+        match self.0.as_ref() {
+            Chain::Empty => self.clone(),
+            Chain::Single(p, below) => {
+                if p.tail.color(below) != Color::Red {
+                    return self.clone();
+                }
+                let (t, c) = Self::repair(p.tail.clone(), below.clone());
+                let p = Packet {
+                    body: p.body.clone(),
+                    tail: t,
+                };
+                Chain::Single(p, c).into()
+            }
+            Chain::Pair(l, r) => Chain::Pair(l.regularize(), r.regularize()).into(),
+        }
+    }
+    /// turn a red triple green by refilling its short buffers from its child deque
+    fn repair(t: Triple<T>, c: Self) -> (Triple<T>, Self) {
+        let (mut t, mut c) = (t, c);
+        if t.kind != Kind::Right && t.pre.len() < 8 {
+            let (d, x) = c.pop_elem().unwrap();
+            (t.pre, c) = match x {
+                Elem::Small(p) => (prepend(&t.pre, &p), d),
+                Elem::Big(p, c1, s) => {
+                    (prepend(&t.pre, &p), c1.append(&d.push_elem(Elem::Small(s))))
+                }
+                Elem::Leaf(_) => unreachable!(),
+            };
+        }
+        if t.kind != Kind::Left && t.suf.len() < 8 && !c.is_empty() {
+            let (d, x) = c.eject_elem().unwrap();
+            (t.suf, c) = match x {
+                Elem::Small(s) => (concat(&s, &t.suf), d),
+                Elem::Big(p, c1, s) => (
+                    concat(&s, &t.suf),
+                    d.inject_elem(Elem::Small(p)).append(&c1),
+                ),
+                Elem::Leaf(_) => unreachable!(),
+            };
+        }
+        debug_assert_eq!(t.color(&c), Color::Green);
+        (t, c)
+    }
+
+    /// all the elements of a deque with at most six of them
+    fn short_elems(&self) -> Option<Vec<Elem<T>>> {
+        let Chain::Single(p, below) = self.0.as_ref() else {
+            return None;
+        };
+        if !below.is_empty() || !p.body.is_empty() || p.tail.pre.len() + p.tail.suf.len() > 6 {
+            return None;
+        }
+        let mut xs = vec![];
+        for d in [&p.tail.pre, &p.tail.suf] {
+            let mut d = d.clone();
+            while let Some((x, e)) = pop(&d) {
+                xs.push(x);
+                d = e;
+            }
+        }
+        Some(xs)
+    }
+    /// the whole deque as a left triple, storing everything but the outer buffers one level
+    /// down
+    fn left_triple(&self) -> (Triple<T>, Self) {
+        if let Chain::Pair(l, r) = self.0.as_ref() {
+            let (t, c) = l.view();
+            let (u, d) = r.view();
+            let (s, s2) = split_last2(&u.suf);
+            if c.is_empty() && t.pre.len() < 8 {
+                // a short prefix would turn red over a non-empty child
+                let pre = prepend(&t.pre, &prepend(&t.suf, &u.pre));
+                return (
+                    Triple::new(Kind::Left, pre, s2),
+                    d.inject_elem(Elem::Small(s)),
+                );
+            }
+            let x = Elem::Big(prepend(&t.suf, &u.pre), d, s);
+            return (Triple::new(Kind::Left, t.pre, s2), c.inject_elem(x));
+        }
+        let (t, c) = self.view();
+        if !c.is_empty() || (t.pre.len() >= 8 && t.suf.len() >= 5) {
+            let (s, s2) = split_last2(&t.suf);
+            let c = c.inject_elem(Elem::Small(s));
+            return (Triple::new(Kind::Left, t.pre, s2), c);
+        }
+        let d = if t.pre.len() < 8 {
+            prepend(&t.pre, &t.suf)
+        } else {
+            concat(&t.pre, &t.suf)
+        };
+        let (d, s2) = split_last2(&d);
+        (Triple::new(Kind::Left, d, s2), c)
+    }
+    /// the whole deque as a right triple, storing everything but the outer buffers one
+    /// level down
+    fn right_triple(&self) -> (Triple<T>, Self) {
+        if let Chain::Pair(l, r) = self.0.as_ref() {
+            let (t, c) = l.view();
+            let (u, d) = r.view();
+            let (p2, p) = split_first2(&t.pre);
+            if d.is_empty() && u.suf.len() < 8 {
+                // a short suffix would turn red over a non-empty child
+                let suf = concat(&concat(&t.suf, &u.pre), &u.suf);
+                return (
+                    Triple::new(Kind::Right, p2, suf),
+                    c.push_elem(Elem::Small(p)),
+                );
+            }
+            let x = Elem::Big(p, c, concat(&t.suf, &u.pre));
+            return (Triple::new(Kind::Right, p2, u.suf), d.push_elem(x));
+        }
+        let (t, c) = self.view();
+        if !c.is_empty() || (t.suf.len() >= 8 && t.pre.len() >= 5) {
+            let (p2, p) = split_first2(&t.pre);
+            let c = c.push_elem(Elem::Small(p));
+            return (Triple::new(Kind::Right, p2, t.suf), c);
+        }
+        let d = if t.suf.len() < 8 {
+            concat(&t.pre, &t.suf)
+        } else {
+            prepend(&t.pre, &t.suf)
+        };
+        let (p2, d) = split_first2(&d);
+        (Triple::new(Kind::Right, p2, d), c)
+    }
+}
+
+impl_deque!(RealTimeCatenableDeque, {
+    fn append(&self, other: &Self) -> Self {
+        CatenableDeque::append(self, other)
+    }
+});
+
+impl<T: Clone + 'static> CatenableDeque<T> for RealTimeCatenableDeque<T> {
+    fn append(&self, other: &Self) -> Self {
+        RealTimeCatenableDeque::append(self, other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deque::Deque;
+    use rand::prelude::*;
+
+    type Deq = RealTimeCatenableDeque<i32>;
+
+    fn steps<U>(f: impl FnOnce() -> U) -> (U, usize) {
+        STEPS.with(|s| s.set(0));
+        let x = f();
+        (x, STEPS.with(|s| s.get()))
+    }
+
+    fn elems(d: &D<i32>) -> Vec<Elem<i32>> {
+        let mut d = d.clone();
+        let mut xs = vec![];
+        while let Some(x) = d.head() {
+            xs.push(x);
+            d = d.tail().unwrap();
+        }
+        xs
+    }
+
+    fn check_elems(d: &D<i32>, level: usize) {
+        for x in elems(d) {
+            match x {
+                Elem::Leaf(_) => assert_eq!(level, 0),
+                Elem::Small(b) => {
+                    assert!(level > 0 && b.len() >= 3);
+                    check_elems(&b, level - 1);
+                }
+                Elem::Big(p, c, s) => {
+                    assert!(level > 0 && p.len() >= 3 && s.len() >= 3);
+                    check_elems(&p, level - 1);
+                    check_deque(&c, level);
+                    check_elems(&s, level - 1);
+                }
+            }
+        }
+    }
+
+    /// colors of the paths from the top triples
+    fn check_deque(d: &Deq, level: usize) -> Vec<Color> {
+        match d.0.as_ref() {
+            Chain::Empty => vec![],
+            Chain::Single(..) => vec![check_triple(d, Kind::Only, level)],
+            Chain::Pair(l, r) => vec![
+                check_triple(l, Kind::Left, level),
+                check_triple(r, Kind::Right, level),
+            ],
+        }
+    }
+
+    fn check_triple(d: &Deq, kind: Kind, level: usize) -> Color {
+        let (t, c) = d.view();
+        assert!(t.kind == kind);
+        check_elems(&t.pre, level);
+        check_elems(&t.suf, level);
+        let (pre, suf) = (t.pre.len(), t.suf.len());
+        match (kind, c.is_empty()) {
+            (Kind::Only, true) => assert!(pre + suf > 0),
+            (Kind::Only, false) => assert!(pre >= 5 && suf >= 5),
+            (Kind::Left, _) => assert!(pre >= 5 && suf == 2),
+            (Kind::Right, _) => assert!(pre == 2 && suf >= 5),
+        }
+        let paths = check_deque(&c, level + 1);
+        let color = t.color(&c);
+        let Chain::Single(p, _) = d.0.as_ref() else {
+            unreachable!()
+        };
+        match (&*p.body, color, c.0.as_ref()) {
+            (Cell::Nil, Color::Green | Color::Red, _) => {}
+            (Cell::Cons(Frame::Only(_), _), Color::Yellow | Color::Orange, Chain::Single(..)) => {}
+            (Cell::Cons(Frame::Left(..), _), Color::Yellow, Chain::Pair(..)) => {}
+            (Cell::Cons(Frame::Right(..), _), Color::Orange, Chain::Pair(..)) => {}
+            _ => panic!("packet does not follow the preferred path"),
+        }
+        match color {
+            Color::Green => Color::Green,
+            Color::Yellow => paths[0],
+            Color::Orange => {
+                assert_eq!(paths[0], Color::Green);
+                *paths.last().unwrap()
+            }
+            Color::Red => {
+                assert!(paths.iter().all(|&c| c == Color::Green));
+                Color::Red
+            }
+        }
+    }
+
+    /// the invariants hold and every path from the top ends green
+    fn check(d: &Deq) {
+        let cs = check_deque(d, 0);
+        assert!(cs.iter().all(|&c| c == Color::Green), "{:?}", cs);
+    }
+
+    #[test]
+    fn test() {
+        let d1 = (0..5).fold(Deq::empty(), |d, x| d.snoc(x));
+        let d2 = (5..10)
+            .rev()
+            .fold(Deq::empty(), |d, x| RealTimeCatenableDeque::cons(x, &d));
+        let d = d1.append(&d2).append(&d1);
+        assert_eq!(d.head(), Some(0));
+        assert_eq!(d.last(), Some(4));
+        assert_eq!(
+            d.iter().collect::<Vec<_>>(),
+            [(0..10).collect::<Vec<_>>(), (0..5).collect()].concat()
+        );
+        assert_eq!(d.init().unwrap().tail().unwrap().len(), 13);
+        assert!(Deq::empty().init().is_none());
+    }
+
+    #[test]
+    fn test_against_naive() {
+        crate::deque::tests::check_against_naive_with(46, check);
+    }
+
+    /// no operation takes more primitive steps than a constant, however long the deque
+    #[test]
+    fn test_steps() {
+        const MAX_STEPS: usize = 100;
+        let mut max = 0;
+        let mut count = |n| max = max.max(n);
+        let mut rng = StdRng::seed_from_u64(47);
+        let mut pool = vec![Deq::empty()];
+        for x in 0..10000 {
+            let d = pool[rng.gen_range(0..pool.len())].clone();
+            let e = &pool[rng.gen_range(0..pool.len())];
+            let (d, n) = steps(|| match rng.gen_range(0..5) {
+                0 => Some(RealTimeCatenableDeque::cons(x, &d)),
+                1 => Some(d.snoc(x)),
+                2 => d.tail(),
+                3 => d.init(),
+                _ => Some(d.append(e)),
+            });
+            count(n);
+            pool.extend(d);
+        }
+        // appending a deque to itself doubles its length up to 400 * 2^60
+        let mut d = (0..400).fold(Deq::empty(), |d, x| d.snoc(x));
+        for i in 0..60 {
+            let (e, n) = steps(|| d.append(&d));
+            count(n);
+            d = e;
+            for _ in 0..i % 7 * 50 {
+                let (e, n) = steps(|| d.tail().unwrap().init().unwrap());
+                count(n);
+                d = e;
+            }
+        }
+        for _ in 0..10000 {
+            let (e, n) = steps(|| d.tail().unwrap().init().unwrap());
+            count(n);
+            d = e;
+        }
+        assert!(max <= MAX_STEPS, "{} steps", max);
+    }
+}
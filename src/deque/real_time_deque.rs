@@ -1,12 +1,19 @@
+use anyhow::{bail, Result};
+
 use crate::{
     lazy,
     lazy::{Stream, StreamCell},
 };
 
-use super::impl_deque;
+use super::{
+    bankers_deque::{stream, Iter},
+    impl_deque,
+};
 
+/// Real-time deque (section 8.4.3) with the same balance as `BankersDeque`. Two steps of
+/// the schedules per operation finish a rotation in time only for `C` up to 4.
 #[derive(Clone)]
-pub struct RealTimeDeque<T> {
+pub struct RealTimeDequeC<T, const C: usize> {
     lenf: usize,
     f: Stream<T>,
     sf: Stream<T>,
@@ -15,7 +22,18 @@ pub struct RealTimeDeque<T> {
     sr: Stream<T>,
 }
 
-impl<T: Clone + 'static> RealTimeDeque<T> {
+/// `RealTimeDequeC` with the balance constant 2 of the book
+pub type RealTimeDeque<T> = RealTimeDequeC<T, 2>;
+
+impl<T: Clone + 'static, const C: usize> RealTimeDequeC<T, C> {
+    /// `C`, rejected at compile time unless between 2 and 4
+    const BALANCE: usize = {
+        assert!(
+            2 <= C && C <= 4,
+            "the balance constant of RealTimeDeque must be between 2 and 4"
+        );
+        C
+    };
     fn new(
         lenf: usize,
         f: Stream<T>,
@@ -46,8 +64,12 @@ impl<T: Clone + 'static> RealTimeDeque<T> {
     pub fn is_empty(&self) -> bool {
         self.lenf + self.lenr == 0
     }
+    /// O(1)
+    pub fn len(&self) -> usize {
+        self.lenf + self.lenr
+    }
     fn check(self) -> Self {
-        let c = 2;
+        let c = Self::BALANCE;
         if self.lenf > c * self.lenr + 1 {
             let i = (self.lenf + self.lenr) / 2;
             let j = self.lenf + self.lenr - i;
@@ -136,9 +158,130 @@ impl<T: Clone + 'static> RealTimeDeque<T> {
             ),
         }
     }
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = T> {
+        Iter {
+            lenf: self.lenf,
+            f: self.f.clone(),
+            lenr: self.lenr,
+            r: self.r.clone(),
+        }
+    }
+    /// O(min(i, n - i)) walking the front or the rear
+    pub fn lookup(&self, i: usize) -> Option<T> {
+        if i < self.lenf {
+            self.f.iter().nth(i)
+        } else if i < self.len() {
+            self.r.iter().nth(self.len() - 1 - i)
+        } else {
+            None
+        }
+    }
+    /// O(min(i, n - i)), rebuilding the cells before the element eagerly so that the
+    /// schedules still cover every suspension
+    pub fn update(&self, i: usize, x: T) -> Result<Self> {
+        if i < self.lenf {
+            Ok(Self {
+                f: self.f.update(i, x)?,
+                ..self.clone()
+            })
+        } else if i < self.len() {
+            Ok(Self {
+                r: self.r.update(self.len() - 1 - i, x)?,
+                ..self.clone()
+            })
+        } else {
+            bail!("invalid index")
+        }
+    }
+    /// O(1) by swapping the front and the rear with their schedules
+    pub fn rev(&self) -> Self {
+        Self::new(
+            self.lenr,
+            self.r.clone(),
+            self.sr.clone(),
+            self.lenf,
+            self.f.clone(),
+            self.sf.clone(),
+        )
+    }
+    /// deque of the given front and rear scheduled in full, rebuilt eagerly in O(n) when
+    /// they are too far out of balance for a rotation to catch up
+    fn balanced(lenf: usize, f: Stream<T>, lenr: usize, r: Stream<T>) -> Self {
+        let c = Self::BALANCE;
+        let (lenf, f, lenr, r) = if lenf > c * lenr + 1 || lenr > c * lenf + 1 {
+            let mut xs = f.iter().take(lenf).collect::<Vec<_>>();
+            xs.extend(r.iter().take(lenr).collect::<Vec<_>>().into_iter().rev());
+            let rs = xs.split_off(xs.len() / 2);
+            (
+                xs.len(),
+                stream(xs.into_iter()),
+                rs.len(),
+                stream(rs.into_iter().rev()),
+            )
+        } else {
+            (lenf, f, lenr, r)
+        };
+        Self::new(lenf, f.clone(), f, lenr, r.clone(), r)
+    }
+    /// O(i) walking the front or O(n - i) walking the rear to the split point, and O(n) when
+    /// a part has to be rebalanced
+    pub fn split_at(&self, i: usize) -> (Self, Self) {
+        let n = self.len();
+        let i = i.min(n);
+        if i <= self.lenf {
+            let front = Self::balanced(i, self.f.take(i), 0, Stream::empty());
+            let back = Self::balanced(self.lenf - i, skip(&self.f, i), self.lenr, self.r.clone());
+            (front, back)
+        } else {
+            let k = n - i;
+            let front = Self::balanced(self.lenf, self.f.clone(), self.lenr - k, skip(&self.r, k));
+            let back = Self::balanced(0, Stream::empty(), k, self.r.take(k));
+            (front, back)
+        }
+    }
+    /// O(m + n), reversing the rear of `self` and the front of `other` eagerly
+    pub fn append(&self, other: &Self) -> Self {
+        let rev = |s: &Stream<T>| stream(s.iter().collect::<Vec<_>>().into_iter().rev());
+        Self::balanced(
+            self.len(),
+            self.f.extend(&rev(&self.r)),
+            other.len(),
+            other.r.extend(&rev(&other.f)),
+        )
+    }
 }
 
-impl_deque!(RealTimeDeque);
+impl_deque!(RealTimeDequeC<const C>, {
+    fn len(&self) -> usize {
+        RealTimeDequeC::len(self)
+    }
+    fn iter(&self) -> impl DoubleEndedIterator<Item = T> {
+        RealTimeDequeC::iter(self)
+    }
+    fn lookup(&self, i: usize) -> Option<T> {
+        RealTimeDequeC::lookup(self, i)
+    }
+    fn update(&self, i: usize, x: T) -> Result<Self> {
+        RealTimeDequeC::update(self, i, x)
+    }
+    fn rev(&self) -> Self {
+        RealTimeDequeC::rev(self)
+    }
+    fn split_at(&self, i: usize) -> (Self, Self) {
+        RealTimeDequeC::split_at(self, i)
+    }
+    fn append(&self, other: &Self) -> Self {
+        RealTimeDequeC::append(self, other)
+    }
+});
+
+/// the stream after the first `n` cells, or the empty rest of a shorter one, evaluating them
+fn skip<T: Clone + 'static>(s: &Stream<T>, n: usize) -> Stream<T> {
+    (0..n).fold(s.clone(), |s, _| match s.force() {
+        StreamCell::Cons(_, xs) => xs,
+        StreamCell::Nil => s,
+    })
+}
 
 fn exec1<T: Clone + 'static>(xs: &Stream<T>) -> Stream<T> {
     match xs.force() {
@@ -156,17 +299,24 @@ fn rotate_rev<T: Clone + 'static>(f: Stream<T>, r: Stream<T>, a: Stream<T>, c: u
         StreamCell::Nil => r.reverse().extend(&a),
         StreamCell::Cons(x, f) => Stream::cons(lazy!((
             x,
-            rotate_rev(f, r.drop(c), r.take(c).reverse().extend(&a), c)
+            rotate_rev(
+                f,
+                skip(&r, c),
+                r.iter().take(c).fold(a, |a, y| Stream::cons(lazy!((y, a)))),
+                c
+            )
         ))),
     }
 }
 
 fn rotate_drop<T: Clone + 'static>(f: Stream<T>, j: usize, r: Stream<T>, c: usize) -> Stream<T> {
     if j < c {
-        rotate_rev(f, r.drop(j), Stream::empty(), c)
+        rotate_rev(f, skip(&r, j), Stream::empty(), c)
     } else {
         match f.force() {
-            StreamCell::Cons(x, f) => Stream::cons(lazy!((x, rotate_drop(f, j - c, r.drop(c), c)))),
+            StreamCell::Cons(x, f) => {
+                Stream::cons(lazy!((x, rotate_drop(f, j - c, skip(&r, c), c))))
+            }
             _ => unreachable!(),
         }
     }
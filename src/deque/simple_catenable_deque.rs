@@ -0,0 +1,236 @@
+use std::sync::Arc;
+
+use crate::{lazy, lazy::Lazy};
+
+use super::{bankers_deque::BankersDeque, impl_deque, CatenableDeque};
+
+/// Simple catenable deque (section 11.2) with O(1) amortized `cons`, `snoc` and
+/// `append` and O(log n) amortized `tail` and `init`. The middle would be a catenable
+/// deque of deques by polymorphic recursion, so every level holds the erased `Elem<T>`.
+#[derive(Clone)]
+pub struct SimpleCatenableDeque<T>(Arc<Node<T>>);
+
+/// element at some depth: a leaf at the top and a deque of the level above below it
+#[derive(Clone)]
+enum Elem<T> {
+    Leaf(T),
+    Deq(D<T>),
+}
+
+type D<T> = BankersDeque<Elem<T>>;
+
+enum Node<T> {
+    Shallow(D<T>),
+    /// front and rear deques have at least two elements
+    Deep(D<T>, Lazy<SimpleCatenableDeque<T>>, D<T>),
+}
+
+impl<T> From<Node<T>> for SimpleCatenableDeque<T> {
+    fn from(x: Node<T>) -> Self {
+        Self(Arc::new(x))
+    }
+}
+
+fn leaf<T>(x: Elem<T>) -> T {
+    match x {
+        Elem::Leaf(x) => x,
+        Elem::Deq(_) => unreachable!(),
+    }
+}
+
+fn deq<T>(x: Elem<T>) -> D<T> {
+    match x {
+        Elem::Deq(d) => d,
+        Elem::Leaf(_) => unreachable!(),
+    }
+}
+
+fn too_small<T: Clone + 'static>(d: &D<T>) -> bool {
+    d.len() < 2
+}
+
+/// `d1` is too small
+fn dappend_l<T: Clone + 'static>(d1: D<T>, d2: D<T>) -> D<T> {
+    match d1.head() {
+        None => d2,
+        Some(x) => BankersDeque::cons(x, &d2),
+    }
+}
+
+/// `d2` is too small
+fn dappend_r<T: Clone + 'static>(d1: D<T>, d2: D<T>) -> D<T> {
+    match d2.head() {
+        None => d1,
+        Some(x) => d1.snoc(x),
+    }
+}
+
+impl<T: Clone + 'static> SimpleCatenableDeque<T> {
+    pub fn empty() -> Self {
+        Node::Shallow(BankersDeque::empty()).into()
+    }
+    pub fn is_empty(&self) -> bool {
+        matches!(self.0.as_ref(), Node::Shallow(d) if d.is_empty())
+    }
+    pub fn cons(x: T, xs: &Self) -> Self {
+        xs.cons_elem(Elem::Leaf(x))
+    }
+    pub fn head(&self) -> Option<T> {
+        self.head_elem().map(leaf)
+    }
+    pub fn tail(&self) -> Option<Self> {
+        match self.0.as_ref() {
+            Node::Shallow(d) => Some(Node::Shallow(d.tail()?).into()),
+            Node::Deep(f, m, r) => {
+                let f = f.tail()?;
+                if !too_small(&f) {
+                    return Some(Node::Deep(f, m.clone(), r.clone()).into());
+                }
+                let m = m.force();
+                Some(match m.head_elem() {
+                    None => Node::Shallow(dappend_l(f, r.clone())).into(),
+                    Some(d) => {
+                        Node::Deep(dappend_l(f, deq(d)), lazy!(m.tail().unwrap()), r.clone()).into()
+                    }
+                })
+            }
+        }
+    }
+    pub fn snoc(&self, x: T) -> Self {
+        self.snoc_elem(Elem::Leaf(x))
+    }
+    pub fn last(&self) -> Option<T> {
+        self.last_elem().map(leaf)
+    }
+    pub fn init(&self) -> Option<Self> {
+        match self.0.as_ref() {
+            Node::Shallow(d) => Some(Node::Shallow(d.init()?).into()),
+            Node::Deep(f, m, r) => {
+                let r = r.init()?;
+                if !too_small(&r) {
+                    return Some(Node::Deep(f.clone(), m.clone(), r).into());
+                }
+                let m = m.force();
+                Some(match m.last_elem() {
+                    None => Node::Shallow(dappend_r(f.clone(), r)).into(),
+                    Some(d) => {
+                        Node::Deep(f.clone(), lazy!(m.init().unwrap()), dappend_r(deq(d), r)).into()
+                    }
+                })
+            }
+        }
+    }
+    /// O(1) amortized
+    pub fn append(&self, other: &Self) -> Self {
+        match (self.0.as_ref(), other.0.as_ref()) {
+            (Node::Shallow(d1), Node::Shallow(d2)) => {
+                if too_small(d1) {
+                    Node::Shallow(dappend_l(d1.clone(), d2.clone())).into()
+                } else if too_small(d2) {
+                    Node::Shallow(dappend_r(d1.clone(), d2.clone())).into()
+                } else {
+                    Node::Deep(d1.clone(), lazy!(Self::empty()), d2.clone()).into()
+                }
+            }
+            (Node::Shallow(d), Node::Deep(f, m, r)) => {
+                if too_small(d) {
+                    Node::Deep(dappend_l(d.clone(), f.clone()), m.clone(), r.clone()).into()
+                } else {
+                    let (f, m) = (f.clone(), m.clone());
+                    Node::Deep(
+                        d.clone(),
+                        lazy!(m.force().cons_elem(Elem::Deq(f))),
+                        r.clone(),
+                    )
+                    .into()
+                }
+            }
+            (Node::Deep(f, m, r), Node::Shallow(d)) => {
+                if too_small(d) {
+                    Node::Deep(f.clone(), m.clone(), dappend_r(r.clone(), d.clone())).into()
+                } else {
+                    let (m, r) = (m.clone(), r.clone());
+                    Node::Deep(
+                        f.clone(),
+                        lazy!(m.force().snoc_elem(Elem::Deq(r))),
+                        d.clone(),
+                    )
+                    .into()
+                }
+            }
+            (Node::Deep(f1, m1, r1), Node::Deep(f2, m2, r2)) => {
+                let (m1, r1, f2, m2) = (m1.clone(), r1.clone(), f2.clone(), m2.clone());
+                Node::Deep(
+                    f1.clone(),
+                    lazy!(m1
+                        .force()
+                        .snoc_elem(Elem::Deq(r1))
+                        .append(&m2.force().cons_elem(Elem::Deq(f2)))),
+                    r2.clone(),
+                )
+                .into()
+            }
+        }
+    }
+    fn cons_elem(&self, x: Elem<T>) -> Self {
+        match self.0.as_ref() {
+            Node::Shallow(d) => Node::Shallow(BankersDeque::cons(x, d)).into(),
+            Node::Deep(f, m, r) => {
+                Node::Deep(BankersDeque::cons(x, f), m.clone(), r.clone()).into()
+            }
+        }
+    }
+    fn snoc_elem(&self, x: Elem<T>) -> Self {
+        match self.0.as_ref() {
+            Node::Shallow(d) => Node::Shallow(d.snoc(x)).into(),
+            Node::Deep(f, m, r) => Node::Deep(f.clone(), m.clone(), r.snoc(x)).into(),
+        }
+    }
+    fn head_elem(&self) -> Option<Elem<T>> {
+        match self.0.as_ref() {
+            Node::Shallow(d) => d.head(),
+            Node::Deep(f, _, _) => f.head(),
+        }
+    }
+    fn last_elem(&self) -> Option<Elem<T>> {
+        match self.0.as_ref() {
+            Node::Shallow(d) => d.last(),
+            Node::Deep(_, _, r) => r.last(),
+        }
+    }
+}
+
+impl_deque!(SimpleCatenableDeque, {
+    fn append(&self, other: &Self) -> Self {
+        CatenableDeque::append(self, other)
+    }
+});
+
+impl<T: Clone + 'static> CatenableDeque<T> for SimpleCatenableDeque<T> {
+    fn append(&self, other: &Self) -> Self {
+        SimpleCatenableDeque::append(self, other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deque::Deque;
+
+    #[test]
+    fn test() {
+        let d1 = (0..5).fold(SimpleCatenableDeque::empty(), |d, x| d.snoc(x));
+        let d2 = (5..10).rev().fold(SimpleCatenableDeque::empty(), |d, x| {
+            SimpleCatenableDeque::cons(x, &d)
+        });
+        let d = d1.append(&d2).append(&d1);
+        assert_eq!(d.head(), Some(0));
+        assert_eq!(d.last(), Some(4));
+        assert_eq!(
+            d.iter().collect::<Vec<_>>(),
+            [(0..10).collect::<Vec<_>>(), (0..5).collect()].concat()
+        );
+        assert_eq!(d.init().unwrap().tail().unwrap().len(), 13);
+        assert!(SimpleCatenableDeque::<i32>::empty().tail().is_none());
+    }
+}
@@ -0,0 +1,365 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
+
+use crate::tree::FiniteMap;
+
+const BITS: u32 = 5;
+const MASK: u64 = (1 << BITS) - 1;
+
+/// Hash array mapped trie (Bagwell, "Ideal Hash Trees")
+#[derive(Clone)]
+pub struct HamtMap<K, V> {
+    len: usize,
+    root: Arc<Node<K, V>>,
+}
+
+enum Node<K, V> {
+    /// bitmap of occupied slots and their entries in slot order
+    Branch(u32, Vec<Entry<K, V>>),
+    /// entries sharing the same full hash
+    Collision(u64, Vec<(K, V)>),
+}
+
+#[derive(Clone)]
+enum Entry<K, V> {
+    Leaf(u64, K, V),
+    Sub(Arc<Node<K, V>>),
+}
+
+fn hash_of<K: Hash>(k: &K) -> u64 {
+    let mut h = DefaultHasher::new();
+    k.hash(&mut h);
+    h.finish()
+}
+
+/// slot bit of `hash` at the level starting from `shift`
+fn bit(hash: u64, shift: u32) -> u32 {
+    1 << ((hash >> shift) & MASK)
+}
+
+/// position of the slot `bit` in the entries of a node with `bitmap`
+fn index(bitmap: u32, bit: u32) -> usize {
+    (bitmap & (bit - 1)).count_ones() as usize
+}
+
+impl<K: Clone + Hash + Eq, V: Clone> Node<K, V> {
+    fn empty() -> Arc<Self> {
+        Arc::new(Self::Branch(0, Vec::new()))
+    }
+    /// node containing two leaves of different hashes
+    fn pair(shift: u32, e1: Entry<K, V>, h1: u64, e2: Entry<K, V>, h2: u64) -> Arc<Self> {
+        let (b1, b2) = (bit(h1, shift), bit(h2, shift));
+        Arc::new(if b1 == b2 {
+            Self::Branch(
+                b1,
+                vec![Entry::Sub(Self::pair(shift + BITS, e1, h1, e2, h2))],
+            )
+        } else if b1 < b2 {
+            Self::Branch(b1 | b2, vec![e1, e2])
+        } else {
+            Self::Branch(b1 | b2, vec![e2, e1])
+        })
+    }
+    fn get(&self, shift: u32, hash: u64, k: &K) -> Option<&V> {
+        match self {
+            Self::Branch(bitmap, es) => {
+                let b = bit(hash, shift);
+                if bitmap & b == 0 {
+                    return None;
+                }
+                match &es[index(*bitmap, b)] {
+                    Entry::Leaf(h, k2, v) if *h == hash && k2 == k => Some(v),
+                    Entry::Leaf(..) => None,
+                    Entry::Sub(n) => n.get(shift + BITS, hash, k),
+                }
+            }
+            Self::Collision(h, kvs) if *h == hash => {
+                kvs.iter().find(|(k2, _)| k2 == k).map(|(_, v)| v)
+            }
+            Self::Collision(..) => None,
+        }
+    }
+    /// returns the new node and whether the key was newly added
+    fn insert(self: &Arc<Self>, shift: u32, hash: u64, k: K, v: V) -> (Arc<Self>, bool) {
+        match self.as_ref() {
+            Self::Branch(bitmap, es) => {
+                let b = bit(hash, shift);
+                let i = index(*bitmap, b);
+                let mut es = es.clone();
+                if bitmap & b == 0 {
+                    es.insert(i, Entry::Leaf(hash, k, v));
+                    return (Arc::new(Self::Branch(bitmap | b, es)), true);
+                }
+                let added = match &es[i] {
+                    Entry::Leaf(h, k2, _) if *h == hash && *k2 == k => {
+                        es[i] = Entry::Leaf(hash, k, v);
+                        false
+                    }
+                    Entry::Leaf(h, k2, v2) if *h == hash => {
+                        let kvs = vec![(k2.clone(), v2.clone()), (k, v)];
+                        es[i] = Entry::Sub(Arc::new(Self::Collision(hash, kvs)));
+                        true
+                    }
+                    Entry::Leaf(h, ..) => {
+                        let h = *h;
+                        let leaf = es[i].clone();
+                        let sub = Self::pair(shift + BITS, leaf, h, Entry::Leaf(hash, k, v), hash);
+                        es[i] = Entry::Sub(sub);
+                        true
+                    }
+                    Entry::Sub(n) => {
+                        let (n, added) = n.insert(shift + BITS, hash, k, v);
+                        es[i] = Entry::Sub(n);
+                        added
+                    }
+                };
+                (Arc::new(Self::Branch(*bitmap, es)), added)
+            }
+            Self::Collision(h, kvs) if *h == hash => {
+                let mut kvs = kvs.clone();
+                let added = match kvs.iter().position(|(k2, _)| *k2 == k) {
+                    Some(i) => {
+                        kvs[i] = (k, v);
+                        false
+                    }
+                    None => {
+                        kvs.push((k, v));
+                        true
+                    }
+                };
+                (Arc::new(Self::Collision(hash, kvs)), added)
+            }
+            Self::Collision(h, _) => {
+                let wrapped =
+                    Arc::new(Self::Branch(bit(*h, shift), vec![Entry::Sub(self.clone())]));
+                wrapped.insert(shift, hash, k, v)
+            }
+        }
+    }
+    /// returns `None` if `k` is absent
+    fn remove(&self, shift: u32, hash: u64, k: &K) -> Option<Arc<Self>> {
+        match self {
+            Self::Branch(bitmap, es) => {
+                let b = bit(hash, shift);
+                if bitmap & b == 0 {
+                    return None;
+                }
+                let i = index(*bitmap, b);
+                let mut es = es.clone();
+                match &es[i] {
+                    Entry::Leaf(h, k2, _) if *h == hash && k2 == k => {
+                        es.remove(i);
+                        return Some(Arc::new(Self::Branch(bitmap & !b, es)));
+                    }
+                    Entry::Leaf(..) => return None,
+                    Entry::Sub(n) => {
+                        let n = n.remove(shift + BITS, hash, k)?;
+                        match n.as_ref() {
+                            Self::Branch(_, ns) if ns.is_empty() => {
+                                es.remove(i);
+                                return Some(Arc::new(Self::Branch(bitmap & !b, es)));
+                            }
+                            Self::Branch(_, ns)
+                                if ns.len() == 1 && matches!(ns[0], Entry::Leaf(..)) =>
+                            {
+                                es[i] = ns[0].clone()
+                            }
+                            Self::Collision(h, kvs) if kvs.len() == 1 => {
+                                es[i] = Entry::Leaf(*h, kvs[0].0.clone(), kvs[0].1.clone())
+                            }
+                            _ => es[i] = Entry::Sub(n),
+                        }
+                    }
+                }
+                Some(Arc::new(Self::Branch(*bitmap, es)))
+            }
+            Self::Collision(h, kvs) if *h == hash => {
+                let i = kvs.iter().position(|(k2, _)| k2 == k)?;
+                let mut kvs = kvs.clone();
+                kvs.remove(i);
+                Some(Arc::new(Self::Collision(hash, kvs)))
+            }
+            Self::Collision(..) => None,
+        }
+    }
+}
+
+impl<K: Clone + Hash + Eq, V: Clone> HamtMap<K, V> {
+    pub fn empty() -> Self {
+        Self {
+            len: 0,
+            root: Node::empty(),
+        }
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    pub fn get(&self, k: &K) -> Option<V> {
+        self.root.get(0, hash_of(k), k).cloned()
+    }
+    pub fn contains_key(&self, k: &K) -> bool {
+        self.root.get(0, hash_of(k), k).is_some()
+    }
+    pub fn insert(&self, k: K, v: V) -> Self {
+        let (root, added) = self.root.insert(0, hash_of(&k), k, v);
+        Self {
+            len: if added { self.len + 1 } else { self.len },
+            root,
+        }
+    }
+    pub fn remove(&self, k: &K) -> Self {
+        match self.root.remove(0, hash_of(k), k) {
+            Some(root) => Self {
+                len: self.len - 1,
+                root,
+            },
+            None => self.clone(),
+        }
+    }
+    /// iterate entries in unspecified order
+    pub fn iter(&self) -> Iter<K, V> {
+        Iter(vec![(self.root.clone(), 0)])
+    }
+}
+
+pub struct Iter<K, V>(Vec<(Arc<Node<K, V>>, usize)>);
+
+impl<K: Clone, V: Clone> Iterator for Iter<K, V> {
+    type Item = (K, V);
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (node, i) = self.0.last_mut()?;
+            let node = node.clone();
+            let pos = *i;
+            *i += 1;
+            match node.as_ref() {
+                Node::Branch(_, es) if pos < es.len() => match &es[pos] {
+                    Entry::Leaf(_, k, v) => return Some((k.clone(), v.clone())),
+                    Entry::Sub(n) => self.0.push((n.clone(), 0)),
+                },
+                Node::Collision(_, kvs) if pos < kvs.len() => return Some(kvs[pos].clone()),
+                _ => {
+                    self.0.pop();
+                }
+            }
+        }
+    }
+}
+
+impl<K: Clone + Hash + Eq, V: Clone> FiniteMap<K, V> for HamtMap<K, V> {
+    fn empty() -> Self {
+        HamtMap::empty()
+    }
+    fn bind(&self, key: K, value: V) -> Self {
+        self.insert(key, value)
+    }
+    fn lookup(&self, key: K) -> Option<V> {
+        self.get(&key)
+    }
+}
+
+#[derive(Clone)]
+pub struct HamtSet<K>(HamtMap<K, ()>);
+
+impl<K: Clone + Hash + Eq> HamtSet<K> {
+    pub fn empty() -> Self {
+        Self(HamtMap::empty())
+    }
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+    pub fn insert(&self, k: K) -> Self {
+        Self(self.0.insert(k, ()))
+    }
+    pub fn member(&self, k: &K) -> bool {
+        self.0.contains_key(k)
+    }
+    pub fn remove(&self, k: &K) -> Self {
+        Self(self.0.remove(k))
+    }
+    pub fn iter(&self) -> impl Iterator<Item = K> {
+        self.0.iter().map(|(k, _)| k)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// key whose hash only depends on `self.0` to force collisions
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    struct Colliding(u8, &'static str);
+
+    impl Hash for Colliding {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            self.0.hash(state)
+        }
+    }
+
+    #[test]
+    fn test_map() {
+        let m = (0..1000).fold(HamtMap::empty(), |m, i| m.insert(i, i * 2));
+        assert_eq!(m.len(), 1000);
+        assert_eq!(m.get(&10), Some(20));
+        assert_eq!(m.get(&1000), None);
+
+        let n = (0..1000).step_by(2).fold(m.clone(), |m, i| m.remove(&i));
+        assert_eq!(n.len(), 500);
+        assert_eq!(n.get(&10), None);
+        assert_eq!(n.get(&11), Some(22));
+        assert_eq!(m.get(&10), Some(20));
+
+        let mut xs = n.iter().collect::<Vec<_>>();
+        xs.sort();
+        assert_eq!(
+            xs,
+            (1..1000).step_by(2).map(|i| (i, i * 2)).collect::<Vec<_>>()
+        );
+
+        let m = m.insert(10, 0);
+        assert_eq!(m.len(), 1000);
+        assert_eq!(m.get(&10), Some(0));
+    }
+
+    #[test]
+    fn test_collision() {
+        let a = Colliding(0, "a");
+        let b = Colliding(0, "b");
+        let c = Colliding(1, "c");
+
+        let m = HamtMap::empty()
+            .insert(a.clone(), 1)
+            .insert(b.clone(), 2)
+            .insert(c.clone(), 3);
+        assert_eq!(m.len(), 3);
+        assert_eq!(m.get(&a), Some(1));
+        assert_eq!(m.get(&b), Some(2));
+        assert_eq!(m.get(&c), Some(3));
+        assert_eq!(m.get(&Colliding(0, "d")), None);
+
+        let m = m.remove(&a);
+        assert_eq!(m.len(), 2);
+        assert_eq!(m.get(&a), None);
+        assert_eq!(m.get(&b), Some(2));
+        assert!(m.remove(&b).remove(&c).is_empty());
+    }
+
+    #[test]
+    fn test_set() {
+        let s = ["x", "y", "z", "x"]
+            .into_iter()
+            .fold(HamtSet::empty(), |s, x| s.insert(x));
+        assert_eq!(s.len(), 3);
+        assert!(s.member(&"y"));
+        assert!(!s.remove(&"y").member(&"y"));
+    }
+}
@@ -1,53 +1,174 @@
+pub mod addressable_heap;
 pub mod binomial_heap;
+pub mod bootstrapped_heap;
+pub mod explicit_min;
 pub mod lazy_binomial_heap;
 pub mod lazy_pairing_heap;
 pub mod leftist_heap;
 pub mod pairing_heap;
+pub mod priority_queue;
 pub mod scheduled_binomial_heap;
+pub mod skew_binomial_heap;
 pub mod splay_heap;
+pub mod weight_biased_leftist_heap;
+
+use std::{cmp::Ordering, collections::BinaryHeap, marker::PhantomData};
 
 use anyhow::Result;
 
-use crate::{stack::Cell, List};
+use crate::{
+    ord::{Compare, Natural},
+    List,
+};
 
 pub trait Heap<T>
 where
     Self: Sized + Clone,
     T: Clone,
 {
+    type Comparator: Compare<T>;
+
     fn empty() -> Self;
     fn is_empty(&self) -> bool;
     fn insert(&self, val: T) -> Self;
     fn merge(&self, other: &Self) -> Self;
     fn find_min(&self) -> Option<T>;
     fn delete_min(&self) -> Result<Self>;
+    /// elements in no particular order, without draining
+    fn iter(&self) -> impl Iterator<Item = T> + '_;
 
-    fn from_list(xs: List<T>) -> Self {
-        fn rec<T: Clone, H: Heap<T>>(xs: List<H>, ys: List<H>) -> H {
-            match &*xs {
-                Cell::Nil => rec(ys, List::empty()),
-                Cell::Cons(x1, xs) => match &**xs {
-                    Cell::Nil => {
-                        if ys.is_empty() {
-                            x1.clone()
-                        } else {
-                            rec(xs.clone(), List::cons(x1.clone(), ys))
-                        }
-                    }
-                    Cell::Cons(x2, xs) => rec(xs.clone(), List::cons(x1.merge(x2), ys)),
-                },
+    /// number of elements, O(n) by traversal unless overridden
+    fn len(&self) -> usize {
+        self.iter().count()
+    }
+    /// whether an element equal to `x` by the comparator is present, O(n)
+    fn contains(&self, x: &T) -> bool {
+        self.iter()
+            .any(|y| Self::Comparator::compare(&y, x) == Ordering::Equal)
+    }
+    /// iterator deleting the minimum element one by one
+    fn into_sorted_iter(self) -> IntoSortedIter<T, Self> {
+        IntoSortedIter(self, PhantomData)
+    }
+    fn into_sorted_vec(self) -> Vec<T> {
+        self.into_sorted_iter().collect()
+    }
+    /// elements in ascending order
+    fn to_list(&self) -> List<T> {
+        self.clone()
+            .into_sorted_vec()
+            .into_iter()
+            .rev()
+            .fold(List::empty(), |xs, x| List::cons(x, xs))
+    }
+    fn heapsort(xs: List<T>) -> List<T> {
+        Self::from_list(xs).to_list()
+    }
+
+    /// merge heaps pairwise in rounds, O(n) merges in total
+    fn merge_all<I: IntoIterator<Item = Self>>(hs: I) -> Self {
+        let mut hs = hs.into_iter().collect::<Vec<_>>();
+        while hs.len() > 1 {
+            let mut it = hs.into_iter();
+            let mut merged = vec![];
+            while let Some(h1) = it.next() {
+                merged.push(match it.next() {
+                    Some(h2) => h1.merge(&h2),
+                    None => h1,
+                });
             }
+            hs = merged;
         }
+        hs.pop().unwrap_or_else(Self::empty)
+    }
+    /// bottom-up construction, O(n) for heaps with O(log n) merge (exercise 3.3)
+    fn from_iter<I: IntoIterator<Item = T>>(xs: I) -> Self {
+        Self::merge_all(xs.into_iter().map(|x| Self::empty().insert(x)))
+    }
+    fn from_list(xs: List<T>) -> Self {
+        Self::from_iter(xs.iter().cloned())
+    }
+}
+
+/// Draining iterator of `Heap::into_sorted_iter`
+pub struct IntoSortedIter<T, H>(H, PhantomData<T>);
+
+impl<T: Clone, H: Heap<T>> Iterator for IntoSortedIter<T, H> {
+    type Item = T;
+    fn next(&mut self) -> Option<T> {
+        let x = self.0.find_min()?;
+        self.0 = self.0.delete_min().ok()?;
+        Some(x)
+    }
+}
+
+/// Subtree of a heap-ordered tree keyed by its root, least root first in a `BinaryHeap`
+struct ByRoot<'a, T, N, C>(&'a T, &'a N, PhantomData<C>);
+
+impl<T, N, C: Compare<T>> PartialEq for ByRoot<'_, T, N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T, N, C: Compare<T>> Eq for ByRoot<'_, T, N, C> {}
+
+impl<T, N, C: Compare<T>> PartialOrd for ByRoot<'_, T, N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T, N, C: Compare<T>> Ord for ByRoot<'_, T, N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        C::compare(other.0, self.0)
+    }
+}
 
-        rec(xs.map(|x| Self::empty().insert(x)), List::empty())
+/// elements of heap-ordered trees in ascending order, taking the least root among the
+/// subtrees reached so far and adding its children, where `root` is `None` for an empty
+/// subtree. O(n log n) like repeated `delete_min` but only reading the trees instead of
+/// rebuilding a heap after every minimum.
+pub(crate) fn sorted_by_roots<'a, T: Clone + 'a, N: 'a, C: Compare<T>>(
+    roots: impl IntoIterator<Item = &'a N>,
+    root: impl Fn(&'a N) -> Option<&'a T>,
+    children: impl Fn(&'a N) -> Vec<&'a N>,
+) -> Vec<T> {
+    let by_root = |n| Some(ByRoot::<T, N, C>(root(n)?, n, PhantomData));
+    let mut frontier = roots
+        .into_iter()
+        .filter_map(by_root)
+        .collect::<BinaryHeap<_>>();
+    let mut xs = vec![];
+    while let Some(ByRoot(x, n, _)) = frontier.pop() {
+        xs.push(x.clone());
+        frontier.extend(children(n).into_iter().filter_map(by_root));
     }
+    xs
+}
+
+/// Type constructor of a heap, for structures holding heaps of their own nodes
+pub trait HeapFamily: 'static {
+    type Heap<U: Clone + Ord + 'static>: Heap<U, Comparator = Natural>;
 }
 
 macro_rules! impl_heap {
-    ($h:ident) => {
-        impl<T: Clone + PartialOrd + 'static> $crate::heap::Heap<T> for $h<T> {
+    ($h:ident, $f:ident) => {
+        impl_heap!($h, $f, {});
+    };
+    ($h:ident, $f:ident, { $($body:tt)* }) => {
+        #[doc = concat!("`HeapFamily` of `", stringify!($h), "`")]
+        pub struct $f;
+
+        impl $crate::heap::HeapFamily for $f {
+            type Heap<U: Clone + Ord + 'static> = $h<U>;
+        }
+
+        impl<T: Clone + 'static, C: $crate::ord::Compare<T>> $crate::heap::Heap<T> for $h<T, C> {
+            type Comparator = C;
+
             fn empty() -> Self {
-                $h::empty()
+                $h::new()
             }
             fn is_empty(&self) -> bool {
                 $h::is_empty(self)
@@ -64,8 +185,139 @@ macro_rules! impl_heap {
             fn delete_min(&self) -> Result<Self> {
                 $h::delete_min(self)
             }
+            fn iter(&self) -> impl Iterator<Item = T> + '_ {
+                $h::iter(self)
+            }
+            $($body)*
         }
     };
 }
 
 pub(crate) use impl_heap;
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        binomial_heap::BinomialHeap, bootstrapped_heap::BootstrappedHeap,
+        explicit_min::ExplicitMin, lazy_binomial_heap::LazyBinomialHeap,
+        lazy_pairing_heap::LazyPairingHeap, leftist_heap::LeftistHeap, pairing_heap::PairingHeap,
+        scheduled_binomial_heap::ScheduledBinomialHeap, skew_binomial_heap::SkewBinomialHeap,
+        splay_heap::SplayHeap, weight_biased_leftist_heap::WeightBiasedLeftistHeap, *,
+    };
+    use crate::ord::Reverse;
+    use rand::prelude::*;
+
+    /// elements by repeated `find_min` and `delete_min`, checking `len` on the way
+    pub(super) fn drain<T: Clone, H: Heap<T>>(h: H) -> Vec<T> {
+        let mut h = h;
+        let mut xs = vec![];
+        while let Some(x) = h.find_min() {
+            let n = h.len();
+            h = h.delete_min().unwrap();
+            assert_eq!(h.len(), n - 1);
+            xs.push(x);
+        }
+        assert!(h.is_empty() && h.delete_min().is_err());
+        xs
+    }
+
+    #[test]
+    fn test_defaults() {
+        let h = [4, 1, 3, 1]
+            .into_iter()
+            .fold(PairingHeap::empty(), |h, x| h.insert(x));
+        assert_eq!(h.len(), 4);
+        assert_eq!(
+            h.to_list().iter().copied().collect::<Vec<_>>(),
+            vec![1, 1, 3, 4]
+        );
+        assert_eq!(
+            h.clone().into_sorted_iter().take(2).collect::<Vec<_>>(),
+            vec![1, 1]
+        );
+        assert_eq!(h.into_sorted_vec(), vec![1, 1, 3, 4]);
+        assert_eq!(PairingHeap::<i32>::empty().len(), 0);
+        assert!(PairingHeap::<i32>::from_list(List::empty()).is_empty());
+    }
+
+    #[test]
+    fn test_bulk() {
+        let xs = [5, 3, 9, 0, 7, 3, 1, 8, 2];
+        let mut ys = xs.to_vec();
+        ys.sort();
+
+        macro_rules! check {
+            ($heap:ident) => {
+                let h = <$heap<_> as Heap<_>>::from_iter(xs);
+                assert_eq!(h.len(), xs.len());
+                assert_eq!(h.into_sorted_vec(), ys);
+                let hs = xs
+                    .chunks(2)
+                    .map(|c| <$heap<_> as Heap<_>>::from_iter(c.to_vec()));
+                assert_eq!($heap::merge_all(hs).into_sorted_vec(), ys);
+                assert!(<$heap<i32> as Heap<_>>::from_iter([]).is_empty());
+                assert!($heap::<i32>::merge_all([]).is_empty());
+            };
+        }
+        check!(BinomialHeap);
+        check!(PairingHeap);
+        check!(LeftistHeap);
+        check!(SkewBinomialHeap);
+    }
+
+    #[test]
+    fn test_sorted_by_roots() {
+        // the overrides walking the trees agree with repeated `delete_min` in either order
+        let mut rng = StdRng::seed_from_u64(0);
+        let xs = (0..500)
+            .map(|_| rng.gen_range(0..100))
+            .collect::<Vec<i32>>();
+
+        macro_rules! check {
+            ($heap:ty) => {
+                let h = <$heap as Heap<_>>::from_iter(xs.iter().copied());
+                let h = h.delete_min().unwrap().merge(&h);
+                assert_eq!(h.clone().into_sorted_vec(), drain(h.clone()));
+                assert_eq!(h.to_list().iter().count(), 2 * xs.len() - 1);
+                assert!(<$heap>::empty().into_sorted_vec().is_empty());
+            };
+        }
+        check!(BinomialHeap<i32>);
+        check!(BinomialHeap<i32, Reverse>);
+        check!(LeftistHeap<i32>);
+        check!(LeftistHeap<i32, Reverse>);
+    }
+
+    #[test]
+    fn test_iter_contains() {
+        let xs = [6, 2, 9, 2, 0, 7, 4, 8, 1];
+        let mut sorted = xs.to_vec();
+        sorted.sort();
+
+        macro_rules! check {
+            ($heap:ty) => {
+                let h = xs.iter().fold(<$heap>::empty(), |h, &x| h.insert(x));
+                let h = h.delete_min().unwrap().insert(0);
+                let mut ys = h.iter().collect::<Vec<_>>();
+                ys.sort();
+                assert_eq!(ys, sorted);
+                assert_eq!(h.len(), xs.len());
+                assert_eq!(h.merge(&h).len(), 2 * xs.len());
+                assert!(h.contains(&7) && h.contains(&0));
+                assert!(!h.contains(&5));
+                assert_eq!(<$heap>::empty().iter().count(), 0);
+            };
+        }
+        check!(LeftistHeap<i32>);
+        check!(WeightBiasedLeftistHeap<i32>);
+        check!(BinomialHeap<i32>);
+        check!(PairingHeap<i32>);
+        check!(SplayHeap<i32>);
+        check!(LazyBinomialHeap<i32>);
+        check!(LazyPairingHeap<i32>);
+        check!(ScheduledBinomialHeap<i32>);
+        check!(SkewBinomialHeap<i32>);
+        check!(BootstrappedHeap<i32>);
+        check!(ExplicitMin<i32>);
+    }
+}
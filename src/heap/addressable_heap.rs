@@ -0,0 +1,257 @@
+use std::marker::PhantomData;
+
+use anyhow::{bail, Result};
+
+use crate::{int_map::IntMap, ord::Natural};
+
+use super::{pairing_heap::PairingHeap, Heap};
+
+/// Key of an element in an `AddressableHeap`, valid in every version derived from the
+/// one that returned it until the element is deleted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Handle(u64);
+
+/// Min-priority queue of values addressable by handles, supporting `decrease_key` and
+/// `remove` of arbitrary elements on top of any heap.
+///
+/// The heap holds `(priority, handle)` pairs and the current priority of each handle
+/// lives in an `IntMap`; updated or removed pairs are left in the heap and skipped
+/// lazily, so the minimum is always a live element. Ties pop in insertion order.
+/// Once the stale pairs outnumber the live ones the heap is rebuilt from the map, which
+/// keeps it within twice the number of elements at an amortized O(1) extra per update.
+#[derive(Clone)]
+pub struct AddressableHeap<P, V, H = PairingHeap<(P, Handle)>> {
+    next: u64,
+    len: usize,
+    /// pairs in `heap` not matching `entries`
+    stale: usize,
+    heap: H,
+    entries: IntMap<(P, V)>,
+    phantom: PhantomData<(P, V)>,
+}
+
+impl<P: Clone + Ord + 'static, V: Clone + 'static> AddressableHeap<P, V> {
+    pub fn empty() -> Self {
+        Self::default()
+    }
+}
+
+impl<P, V, H> Default for AddressableHeap<P, V, H>
+where
+    P: Clone + Ord,
+    V: Clone,
+    H: Heap<(P, Handle), Comparator = Natural>,
+{
+    fn default() -> Self {
+        Self {
+            next: 0,
+            len: 0,
+            stale: 0,
+            heap: H::empty(),
+            entries: IntMap::empty(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<P, V, H> AddressableHeap<P, V, H>
+where
+    P: Clone + Ord,
+    V: Clone,
+    H: Heap<(P, Handle), Comparator = Natural>,
+{
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    fn is_live(&self, (p, h): &(P, Handle)) -> bool {
+        matches!(self.entries.lookup(h.0), Some((q, _)) if q == *p)
+    }
+    /// drop stale pairs from the top of the heap, or all of them once they outnumber the
+    /// live ones
+    fn prune(mut self) -> Self {
+        if self.stale > self.len {
+            self.heap = H::from_iter(self.entries.iter().map(|(k, (p, _))| (p, Handle(k))));
+            self.stale = 0;
+            return self;
+        }
+        while let Some(x) = self.heap.find_min() {
+            if self.is_live(&x) {
+                break;
+            }
+            self.heap = self.heap.delete_min().unwrap();
+            self.stale -= 1;
+        }
+        self
+    }
+    pub fn insert(&self, priority: P, value: V) -> (Handle, Self) {
+        let h = Handle(self.next);
+        let q = Self {
+            next: self.next + 1,
+            len: self.len + 1,
+            stale: self.stale,
+            heap: self.heap.insert((priority.clone(), h)),
+            entries: self.entries.insert(h.0, (priority, value)),
+            phantom: PhantomData,
+        };
+        (h, q)
+    }
+    pub fn get(&self, handle: Handle) -> Option<(P, V)> {
+        self.entries.lookup(handle.0)
+    }
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.entries.member(handle.0)
+    }
+    pub fn find_min(&self) -> Option<(Handle, P, V)> {
+        let (p, h) = self.heap.find_min()?;
+        let (_, v) = self.entries.lookup(h.0)?;
+        Some((h, p, v))
+    }
+    pub fn delete_min(&self) -> Result<Self> {
+        match self.find_min() {
+            None => bail!("empty"),
+            Some((h, ..)) => self.remove(h),
+        }
+    }
+    /// lower the priority of `handle`; fails if it is absent or `priority` is larger
+    pub fn decrease_key(&self, handle: Handle, priority: P) -> Result<Self> {
+        let Some((p, v)) = self.get(handle) else {
+            bail!("invalid handle")
+        };
+        if priority > p {
+            bail!("priority increased")
+        }
+        if priority == p {
+            return Ok(self.clone());
+        }
+        Ok(Self {
+            next: self.next,
+            len: self.len,
+            stale: self.stale + 1,
+            heap: self.heap.insert((priority.clone(), handle)),
+            entries: self.entries.insert(handle.0, (priority, v)),
+            phantom: PhantomData,
+        }
+        .prune())
+    }
+    pub fn remove(&self, handle: Handle) -> Result<Self> {
+        if !self.contains(handle) {
+            bail!("invalid handle")
+        }
+        Ok(Self {
+            next: self.next,
+            len: self.len - 1,
+            stale: self.stale + 1,
+            heap: self.heap.clone(),
+            entries: self.entries.remove(handle.0),
+            phantom: PhantomData,
+        }
+        .prune())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heap::{binomial_heap::BinomialHeap, leftist_heap::LeftistHeap};
+
+    fn drain<H: Heap<(u32, Handle), Comparator = Natural>>(
+        q: AddressableHeap<u32, char, H>,
+    ) -> Vec<(u32, char)> {
+        let mut q = q;
+        let mut xs = vec![];
+        while let Some((_, p, v)) = q.find_min() {
+            xs.push((p, v));
+            q = q.delete_min().unwrap();
+        }
+        assert!(q.is_empty());
+        xs
+    }
+
+    #[test]
+    fn test() {
+        macro_rules! check {
+            ($heap:ident) => {
+                let q = AddressableHeap::<_, _, $heap<_>>::default();
+                let (a, q) = q.insert(5, 'a');
+                let (b, q) = q.insert(3, 'b');
+                let (c, q) = q.insert(7, 'c');
+                let (d, q) = q.insert(3, 'd');
+                assert_eq!(q.len(), 4);
+                assert_eq!(q.find_min(), Some((b, 3, 'b')));
+
+                let q1 = q.decrease_key(c, 1).unwrap().remove(b).unwrap();
+                assert_eq!(q1.len(), 3);
+                assert_eq!(q1.get(c), Some((1, 'c')));
+                assert!(!q1.contains(b));
+                assert!(q1.decrease_key(a, 6).is_err());
+                assert!(q1.remove(b).is_err());
+                assert_eq!(drain(q1), vec![(1, 'c'), (3, 'd'), (5, 'a')]);
+
+                // the original version is untouched
+                let q2 = q.decrease_key(d, 3).unwrap();
+                assert_eq!(drain(q2), vec![(3, 'b'), (3, 'd'), (5, 'a'), (7, 'c')]);
+                assert!(q.remove(a).unwrap().delete_min().unwrap().contains(d));
+            };
+        }
+        check!(PairingHeap);
+        check!(BinomialHeap);
+        check!(LeftistHeap);
+    }
+
+    #[test]
+    fn test_stale() {
+        let mut q = AddressableHeap::<_, _, LeftistHeap<_>>::default();
+        let mut handles = vec![];
+        for v in 0..100 {
+            let (h, qp) = q.insert(1000, v);
+            handles.push(h);
+            q = qp;
+        }
+        for p in (0..1000).rev() {
+            for &h in &handles[..50] {
+                q = q.decrease_key(h, p).unwrap();
+                assert!(q.heap.len() <= 2 * q.len());
+            }
+        }
+        for &h in &handles[..99] {
+            q = q.remove(h).unwrap();
+            assert!(q.heap.len() <= 2 * q.len());
+        }
+        assert_eq!(q.find_min(), Some((handles[99], 1000, 99)));
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        // shortest distances from 0 in a small weighted graph
+        let edges: [&[(usize, u32)]; 5] = [
+            &[(1, 4), (2, 1)],
+            &[(3, 1)],
+            &[(1, 2), (3, 5)],
+            &[(4, 3)],
+            &[],
+        ];
+        let mut handles = vec![];
+        let mut q = AddressableHeap::empty();
+        for v in 0..edges.len() {
+            let (h, qp) = q.insert(if v == 0 { 0 } else { u32::MAX }, v);
+            handles.push(h);
+            q = qp;
+        }
+        let mut dist = vec![0; edges.len()];
+        while let Some((_, d, v)) = q.find_min() {
+            dist[v] = d;
+            q = q.delete_min().unwrap();
+            for &(w, c) in edges[v] {
+                if let Some((dw, _)) = q.get(handles[w]) {
+                    if d + c < dw {
+                        q = q.decrease_key(handles[w], d + c).unwrap();
+                    }
+                }
+            }
+        }
+        assert_eq!(dist, vec![0, 3, 1, 4, 7]);
+    }
+}
@@ -1,9 +1,12 @@
 use anyhow::{bail, Result};
-use std::{cmp::Ordering, sync::Arc};
+use std::{cmp::Ordering, marker::PhantomData, sync::Arc};
 
-use crate::List;
+use crate::{
+    ord::{Compare, Natural},
+    List,
+};
 
-use super::impl_heap;
+use super::{impl_heap, sorted_by_roots};
 
 struct Tree<T> {
     rank: usize,
@@ -11,7 +14,7 @@ struct Tree<T> {
     children: List<Arc<Tree<T>>>,
 }
 
-impl<T: Clone + PartialOrd> Tree<T> {
+impl<T: Clone> Tree<T> {
     fn node(rank: usize, elem: T, children: List<Arc<Tree<T>>>) -> Arc<Tree<T>> {
         Arc::new(Tree {
             rank,
@@ -22,8 +25,8 @@ impl<T: Clone + PartialOrd> Tree<T> {
     fn root(&self) -> T {
         self.elem.clone()
     }
-    fn link(self: Arc<Self>, other: Arc<Self>) -> Arc<Tree<T>> {
-        if self.elem <= other.elem {
+    fn link<C: Compare<T>>(self: Arc<Self>, other: Arc<Self>) -> Arc<Tree<T>> {
+        if C::le(&self.elem, &other.elem) {
             Self::node(
                 self.rank + 1,
                 self.elem.clone(),
@@ -40,25 +43,48 @@ impl<T: Clone + PartialOrd> Tree<T> {
 }
 
 #[derive(Clone)]
-pub struct BinomialHeap<T>(List<Arc<Tree<T>>>);
+pub struct BinomialHeap<T, C = Natural>(List<Arc<Tree<T>>>, PhantomData<C>);
 
-impl<T: Clone + PartialOrd> BinomialHeap<T> {
+impl<T: Clone + Ord> BinomialHeap<T> {
+    /// empty min-heap; use `Heap::empty` for other orders
     pub fn empty() -> Self {
-        Self(List::empty())
+        Self::new()
+    }
+}
+
+impl<T: Clone, C: Compare<T>> BinomialHeap<T, C> {
+    fn new() -> Self {
+        Self::from_trees(List::empty())
+    }
+    fn from_trees(ts: List<Arc<Tree<T>>>) -> Self {
+        Self(ts, PhantomData)
     }
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
+    /// elements in no particular order, without draining
+    pub fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        let mut stack = self.0.iter().collect::<Vec<_>>();
+        std::iter::from_fn(move || {
+            let t = stack.pop()?;
+            stack.extend(t.children.iter());
+            Some(t.root())
+        })
+    }
+    /// O(log n), a tree of rank r has 2^r elements
+    pub fn len(&self) -> usize {
+        self.0.iter().map(|t| 1 << t.rank).sum()
+    }
     fn ins_tree(&self, t: Arc<Tree<T>>) -> Self {
         if self.is_empty() {
-            Self(List::cons(t, List::empty()))
+            Self::from_trees(List::cons(t, List::empty()))
         } else {
             let tp = self.0.head().unwrap();
             let tsp = self.0.tail().unwrap();
             if t.rank < tp.rank {
-                Self(List::cons(t, self.0.clone()))
+                Self::from_trees(List::cons(t, self.0.clone()))
             } else {
-                Self(tsp).ins_tree(t.link(tp))
+                Self::from_trees(tsp).ins_tree(t.link::<C>(tp))
             }
         }
     }
@@ -76,9 +102,15 @@ impl<T: Clone + PartialOrd> BinomialHeap<T> {
             let t2 = other.0.head().unwrap();
             let ts2 = other.0.tail().unwrap();
             match t1.rank.cmp(&t2.rank) {
-                Ordering::Less => Self(List::cons(t1, other.merge(&Self(ts1)).0)),
-                Ordering::Greater => Self(List::cons(t2, self.merge(&Self(ts2)).0)),
-                Ordering::Equal => Self(ts1).merge(&Self(ts2)).ins_tree(t1.link(t2)),
+                Ordering::Less => {
+                    Self::from_trees(List::cons(t1, other.merge(&Self::from_trees(ts1)).0))
+                }
+                Ordering::Greater => {
+                    Self::from_trees(List::cons(t2, self.merge(&Self::from_trees(ts2)).0))
+                }
+                Ordering::Equal => Self::from_trees(ts1)
+                    .merge(&Self::from_trees(ts2))
+                    .ins_tree(t1.link::<C>(t2)),
             }
         }
     }
@@ -86,16 +118,16 @@ impl<T: Clone + PartialOrd> BinomialHeap<T> {
         if self.is_empty() {
             bail!("Empty");
         } else if self.0.tail().unwrap().is_empty() {
-            Ok((self.0.head().unwrap(), Self::empty()))
+            Ok((self.0.head().unwrap(), Self::new()))
         } else {
             let t = self.0.head().unwrap();
             let ts = self.0.tail().unwrap();
-            let (tp, tsp) = Self(ts.clone()).remove_min_tree().unwrap();
+            let (tp, tsp) = Self::from_trees(ts.clone()).remove_min_tree().unwrap();
 
-            if t.root() <= tp.root() {
-                Ok((t, Self(ts)))
+            if C::le(&t.root(), &tp.root()) {
+                Ok((t, Self::from_trees(ts)))
             } else {
-                Ok((tp, Self(List::cons(t, tsp.0))))
+                Ok((tp, Self::from_trees(List::cons(t, tsp.0))))
             }
         }
     }
@@ -105,15 +137,52 @@ impl<T: Clone + PartialOrd> BinomialHeap<T> {
     pub fn delete_min(&self) -> Result<Self> {
         let (t, ts2) = self.remove_min_tree()?;
         let ts1 = t.children.clone();
-        Ok(Self(ts1.reverse()).merge(&ts2))
+        Ok(Self::from_trees(ts1.reverse()).merge(&ts2))
     }
 }
 
-impl_heap!(BinomialHeap);
+impl_heap!(BinomialHeap, BinomialHeapFamily, {
+    fn len(&self) -> usize {
+        BinomialHeap::len(self)
+    }
+    /// O(n log n) by walking the trees, see `sorted_by_roots`
+    fn into_sorted_vec(self) -> Vec<T> {
+        sorted_by_roots::<_, _, C>(
+            self.0.iter().map(Arc::as_ref),
+            |t| Some(&t.elem),
+            |t| t.children.iter().map(Arc::as_ref).collect(),
+        )
+    }
+    /// count up the binary digits of trees directly, O(n)
+    fn from_iter<I: IntoIterator<Item = T>>(xs: I) -> Self {
+        let mut digits: Vec<Option<Arc<Tree<T>>>> = vec![];
+        for x in xs {
+            let mut t = Tree::node(0, x, List::empty());
+            for d in digits.iter_mut() {
+                match d.take() {
+                    None => break,
+                    Some(tp) => t = tp.link::<C>(t),
+                }
+            }
+            match digits.get_mut(t.rank) {
+                Some(d) => *d = Some(t),
+                None => digits.push(Some(t)),
+            }
+        }
+        Self::from_trees(
+            digits
+                .into_iter()
+                .rev()
+                .flatten()
+                .fold(List::empty(), |ts, t| List::cons(t, ts)),
+        )
+    }
+});
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::heap::Heap;
 
     #[test]
     fn test_heap() {
@@ -126,4 +195,14 @@ mod tests {
         let h = h.delete_min().unwrap();
         assert_eq!(h.find_min(), Some(10));
     }
+
+    #[test]
+    fn test_len() {
+        let h = (0..13)
+            .rev()
+            .fold(BinomialHeap::empty(), |h, x| h.insert(x));
+        assert_eq!(h.len(), 13);
+        assert_eq!(h.delete_min().unwrap().len(), 12);
+        assert_eq!(h.into_sorted_vec(), (0..13).collect::<Vec<_>>());
+    }
 }
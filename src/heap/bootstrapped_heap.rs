@@ -0,0 +1,225 @@
+use std::{cmp::Ordering, marker::PhantomData, sync::Arc};
+
+use anyhow::{bail, Result};
+
+use crate::ord::{Compare, Natural};
+
+use super::{skew_binomial_heap::SkewBinomialHeapFamily, Heap, HeapFamily};
+
+/// Heap bootstrapped from a primitive heap `F` of bootstrapped heaps (section 10.2.2).
+/// `find_min`, `insert` and `merge` take O(1) and `delete_min` takes the cost of the
+/// primitive `delete_min`.
+pub struct BootstrappedHeap<T, F = SkewBinomialHeapFamily, C = Natural>(
+    Arc<Tree<T, F, C>>,
+    PhantomData<C>,
+)
+where
+    T: Clone + 'static,
+    F: HeapFamily,
+    C: Compare<T>;
+
+enum Tree<T, F, C>
+where
+    T: Clone + 'static,
+    F: HeapFamily,
+    C: Compare<T>,
+{
+    Empty,
+    /// size, minimum and the primitive heap of the other subheaps
+    Node(usize, T, F::Heap<Rooted<T, F, C>>),
+}
+
+/// Non-empty bootstrapped heap ordered by its minimum, as an element of the primitive heap
+struct Rooted<T, F, C>(BootstrappedHeap<T, F, C>)
+where
+    T: Clone + 'static,
+    F: HeapFamily,
+    C: Compare<T>;
+
+// implement manually because derive impl requires F to be Clone
+impl<T: Clone + 'static, F: HeapFamily, C: Compare<T>> Clone for BootstrappedHeap<T, F, C> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone(), PhantomData)
+    }
+}
+
+impl<T: Clone + 'static, F: HeapFamily, C: Compare<T>> Clone for Rooted<T, F, C> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T: Clone + 'static, F: HeapFamily, C: Compare<T>> Rooted<T, F, C> {
+    fn root(&self) -> &T {
+        match self.0 .0.as_ref() {
+            Tree::Node(_, x, _) => x,
+            Tree::Empty => unreachable!(),
+        }
+    }
+}
+
+impl<T: Clone + 'static, F: HeapFamily, C: Compare<T>> PartialEq for Rooted<T, F, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: Clone + 'static, F: HeapFamily, C: Compare<T>> Eq for Rooted<T, F, C> {}
+
+impl<T: Clone + 'static, F: HeapFamily, C: Compare<T>> PartialOrd for Rooted<T, F, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Clone + 'static, F: HeapFamily, C: Compare<T>> Ord for Rooted<T, F, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        C::compare(self.root(), other.root())
+    }
+}
+
+impl<T: Clone + Ord + 'static, F: HeapFamily> BootstrappedHeap<T, F> {
+    /// empty min-heap; use `Heap::empty` for other orders
+    pub fn empty() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone + 'static, F: HeapFamily, C: Compare<T>> BootstrappedHeap<T, F, C> {
+    fn new() -> Self {
+        Self(Arc::new(Tree::Empty), PhantomData)
+    }
+    fn node(n: usize, x: T, p: F::Heap<Rooted<T, F, C>>) -> Self {
+        Self(Arc::new(Tree::Node(n, x, p)), PhantomData)
+    }
+    /// O(1)
+    pub fn len(&self) -> usize {
+        match self.0.as_ref() {
+            Tree::Empty => 0,
+            Tree::Node(n, ..) => *n,
+        }
+    }
+    pub fn is_empty(&self) -> bool {
+        matches!(self.0.as_ref(), Tree::Empty)
+    }
+    pub fn merge(&self, other: &Self) -> Self {
+        match (self.0.as_ref(), other.0.as_ref()) {
+            (Tree::Empty, _) => other.clone(),
+            (_, Tree::Empty) => self.clone(),
+            (Tree::Node(n1, x, p1), Tree::Node(n2, y, p2)) => {
+                if C::le(x, y) {
+                    Self::node(n1 + n2, x.clone(), p1.insert(Rooted(other.clone())))
+                } else {
+                    Self::node(n1 + n2, y.clone(), p2.insert(Rooted(self.clone())))
+                }
+            }
+        }
+    }
+    pub fn insert(&self, x: T) -> Self {
+        Self::node(1, x, F::Heap::empty()).merge(self)
+    }
+    /// elements in no particular order, without draining
+    pub fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        let mut stack = vec![self.clone()];
+        std::iter::from_fn(move || loop {
+            if let Tree::Node(_, x, p) = stack.pop()?.0.as_ref() {
+                stack.extend(p.iter().map(|Rooted(h)| h));
+                return Some(x.clone());
+            }
+        })
+    }
+    pub fn find_min(&self) -> Option<T> {
+        match self.0.as_ref() {
+            Tree::Empty => None,
+            Tree::Node(_, x, _) => Some(x.clone()),
+        }
+    }
+    pub fn delete_min(&self) -> Result<Self> {
+        match self.0.as_ref() {
+            Tree::Empty => bail!("empty"),
+            Tree::Node(n, _, p) => match p.find_min() {
+                None => Ok(Self::new()),
+                Some(Rooted(h)) => match h.0.as_ref() {
+                    Tree::Node(_, y, p1) => {
+                        Ok(Self::node(n - 1, y.clone(), p1.merge(&p.delete_min()?)))
+                    }
+                    Tree::Empty => unreachable!(),
+                },
+            },
+        }
+    }
+}
+
+impl<T: Clone + 'static, F: HeapFamily, C: Compare<T>> Heap<T> for BootstrappedHeap<T, F, C> {
+    type Comparator = C;
+
+    fn empty() -> Self {
+        Self::new()
+    }
+    fn is_empty(&self) -> bool {
+        BootstrappedHeap::is_empty(self)
+    }
+    fn insert(&self, x: T) -> Self {
+        BootstrappedHeap::insert(self, x)
+    }
+    fn merge(&self, other: &Self) -> Self {
+        BootstrappedHeap::merge(self, other)
+    }
+    fn find_min(&self) -> Option<T> {
+        BootstrappedHeap::find_min(self)
+    }
+    fn delete_min(&self) -> Result<Self> {
+        BootstrappedHeap::delete_min(self)
+    }
+    fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        BootstrappedHeap::iter(self)
+    }
+    fn len(&self) -> usize {
+        BootstrappedHeap::len(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heap::tests::drain;
+    use crate::{
+        heap::{
+            binomial_heap::BinomialHeapFamily, leftist_heap::LeftistHeapFamily,
+            pairing_heap::PairingHeapFamily,
+        },
+        ord::Reverse,
+    };
+
+    #[test]
+    fn test() {
+        let xs = [3, 1, 4, 1, 5, 9, 2, 6];
+        let h = xs
+            .into_iter()
+            .fold(BootstrappedHeap::<_>::empty(), |h, x| h.insert(x));
+        assert_eq!(drain(h), vec![1, 1, 2, 3, 4, 5, 6, 9]);
+
+        macro_rules! check {
+            ($f:ident) => {
+                let h1 = xs
+                    .into_iter()
+                    .fold(BootstrappedHeap::<_, $f>::empty(), |h, x| h.insert(x));
+                let h2 = [8, 0, 7]
+                    .into_iter()
+                    .fold(BootstrappedHeap::<_, $f>::empty(), |h, x| h.insert(x));
+                assert_eq!(drain(h1.merge(&h2)), vec![0, 1, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+            };
+        }
+        check!(SkewBinomialHeapFamily);
+        check!(BinomialHeapFamily);
+        check!(LeftistHeapFamily);
+        check!(PairingHeapFamily);
+    }
+
+    #[test]
+    fn test_reverse() {
+        let h: BootstrappedHeap<_, SkewBinomialHeapFamily, Reverse> = Heap::empty();
+        let h = [3, 1, 4, 1, 5].into_iter().fold(h, |h, x| h.insert(x));
+        assert_eq!(drain(h), vec![5, 4, 3, 1, 1]);
+    }
+}
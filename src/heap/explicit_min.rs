@@ -0,0 +1,166 @@
+use std::marker::PhantomData;
+
+use anyhow::{bail, Result};
+
+use crate::ord::Compare;
+
+use super::{binomial_heap::BinomialHeap, Heap};
+
+/// Heap caching its minimum element for O(1) `find_min` (exercise 3.7)
+#[derive(Clone)]
+pub struct ExplicitMin<T, H = BinomialHeap<T>> {
+    min: Option<T>,
+    heap: H,
+    phantom: PhantomData<T>,
+}
+
+impl<T: Clone + Ord + 'static> ExplicitMin<T> {
+    /// empty min-heap over `BinomialHeap`; use `Heap::empty` for other heaps
+    pub fn empty() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone, H: Heap<T>> ExplicitMin<T, H> {
+    fn new() -> Self {
+        Self::from_heap(H::empty())
+    }
+    /// wrap `heap`, taking its minimum once
+    pub fn from_heap(heap: H) -> Self {
+        Self {
+            min: heap.find_min(),
+            heap,
+            phantom: PhantomData,
+        }
+    }
+    /// unwrap the underlying heap
+    pub fn into_heap(self) -> H {
+        self.heap
+    }
+    pub fn is_empty(&self) -> bool {
+        self.min.is_none()
+    }
+    fn min_of(x: &Option<T>, y: &Option<T>) -> Option<T> {
+        match (x, y) {
+            (None, _) => y.clone(),
+            (_, None) => x.clone(),
+            (Some(x), Some(y)) => {
+                if H::Comparator::le(x, y) {
+                    Some(x.clone())
+                } else {
+                    Some(y.clone())
+                }
+            }
+        }
+    }
+    pub fn insert(&self, x: T) -> Self {
+        Self {
+            min: Self::min_of(&self.min, &Some(x.clone())),
+            heap: self.heap.insert(x),
+            phantom: PhantomData,
+        }
+    }
+    pub fn merge(&self, other: &Self) -> Self {
+        Self {
+            min: Self::min_of(&self.min, &other.min),
+            heap: self.heap.merge(&other.heap),
+            phantom: PhantomData,
+        }
+    }
+    /// O(1)
+    pub fn find_min(&self) -> Option<T> {
+        self.min.clone()
+    }
+    pub fn delete_min(&self) -> Result<Self> {
+        if self.is_empty() {
+            bail!("empty")
+        }
+        Ok(Self::from_heap(self.heap.delete_min()?))
+    }
+}
+
+impl<T: Clone, H: Heap<T>> Heap<T> for ExplicitMin<T, H> {
+    type Comparator = H::Comparator;
+
+    fn empty() -> Self {
+        Self::new()
+    }
+    fn is_empty(&self) -> bool {
+        ExplicitMin::is_empty(self)
+    }
+    fn insert(&self, x: T) -> Self {
+        ExplicitMin::insert(self, x)
+    }
+    fn merge(&self, other: &Self) -> Self {
+        ExplicitMin::merge(self, other)
+    }
+    fn find_min(&self) -> Option<T> {
+        ExplicitMin::find_min(self)
+    }
+    fn delete_min(&self) -> Result<Self> {
+        ExplicitMin::delete_min(self)
+    }
+    fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        self.heap.iter()
+    }
+    fn len(&self) -> usize {
+        self.heap.len()
+    }
+    fn contains(&self, x: &T) -> bool {
+        self.heap.contains(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heap::tests::drain;
+    use crate::{
+        heap::{
+            leftist_heap::LeftistHeap, pairing_heap::PairingHeap,
+            skew_binomial_heap::SkewBinomialHeap, splay_heap::SplayHeap,
+        },
+        ord::Reverse,
+    };
+
+    #[test]
+    fn test() {
+        let h = [3, 1, 4, 1, 5]
+            .into_iter()
+            .fold(ExplicitMin::empty(), |h, x| h.insert(x));
+        assert_eq!(h.find_min(), Some(1));
+        assert_eq!(drain(h.clone()), vec![1, 1, 3, 4, 5]);
+        assert_eq!(h.into_heap().find_min(), Some(1));
+
+        macro_rules! check {
+            ($heap:ident) => {
+                let h1 = [5, 2, 8]
+                    .into_iter()
+                    .fold(ExplicitMin::<_, $heap<_>>::new(), |h, x| h.insert(x));
+                let h2 = [7, 0, 3]
+                    .into_iter()
+                    .fold(ExplicitMin::<_, $heap<_>>::new(), |h, x| h.insert(x));
+                let h = h1.merge(&h2);
+                assert_eq!(h.find_min(), Some(0));
+                assert_eq!(drain(h), vec![0, 2, 3, 5, 7, 8]);
+                let h = ExplicitMin::<_, $heap<_, Reverse>>::new()
+                    .insert(1)
+                    .insert(2);
+                assert_eq!(drain(h), vec![2, 1]);
+            };
+        }
+        check!(LeftistHeap);
+        check!(BinomialHeap);
+        check!(PairingHeap);
+        check!(SplayHeap);
+        check!(SkewBinomialHeap);
+    }
+
+    #[test]
+    fn test_empty() {
+        let h = ExplicitMin::<i32>::empty();
+        assert!(h.is_empty());
+        assert_eq!(h.find_min(), None);
+        assert!(h.delete_min().is_err());
+    }
+}
@@ -1,13 +1,20 @@
-use std::cmp::Ordering;
+use std::{cmp::Ordering, marker::PhantomData};
 
 use anyhow::{bail, Result};
 
-use crate::{lazy, lazy::Lazy, stack::Cell, List};
+use crate::{
+    lazy,
+    lazy::Lazy,
+    ord::{Compare, Natural},
+    stack::Cell,
+    List,
+};
 
 use super::impl_heap;
 
+/// suspended trees and the number of elements
 #[derive(Clone)]
-pub struct LazyBinomialHeap<T>(Lazy<List<Tree<T>>>);
+pub struct LazyBinomialHeap<T, C = Natural>(Lazy<List<Tree<T>>>, usize, PhantomData<C>);
 
 #[derive(Clone)]
 struct Tree<T> {
@@ -16,44 +23,79 @@ struct Tree<T> {
     children: List<Tree<T>>,
 }
 
-impl<T: Clone + PartialOrd + 'static> LazyBinomialHeap<T> {
+impl<T: Clone + Ord + 'static> LazyBinomialHeap<T> {
+    /// empty min-heap; use `Heap::empty` for other orders
     pub fn empty() -> Self {
-        Self(lazy!(List::empty()))
+        Self::new()
+    }
+}
+
+impl<T: Clone + 'static, C: Compare<T>> LazyBinomialHeap<T, C> {
+    fn new() -> Self {
+        Self(lazy!(List::empty()), 0, PhantomData)
     }
     pub fn is_empty(&self) -> bool {
-        self.0.force().is_empty()
+        self.1 == 0
+    }
+    /// O(1)
+    pub fn len(&self) -> usize {
+        self.1
+    }
+    /// elements in no particular order, without draining
+    pub fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        let mut stack = self.0.force().iter().cloned().collect::<Vec<_>>();
+        std::iter::from_fn(move || {
+            let t = stack.pop()?;
+            stack.extend(t.children.iter().cloned());
+            Some(t.val)
+        })
     }
     pub fn insert(&self, x: T) -> Self {
         let this = self.clone();
-        Self(lazy!(ins_tree(
-            Tree {
-                rank: 0,
-                val: x.clone(),
-                children: List::empty()
-            },
-            this.0.force()
-        )))
+        Self(
+            lazy!(ins_tree::<T, C>(
+                Tree {
+                    rank: 0,
+                    val: x.clone(),
+                    children: List::empty()
+                },
+                this.0.force()
+            )),
+            self.1 + 1,
+            PhantomData,
+        )
     }
     pub fn merge(&self, other: &Self) -> Self {
         let this = self.clone();
         let other = other.clone();
-        Self(lazy!(merge(this.0.force(), other.0.force())))
+        Self(
+            lazy!(merge::<T, C>(this.0.force(), other.0.force())),
+            self.1 + other.1,
+            PhantomData,
+        )
     }
     pub fn find_min(&self) -> Option<T> {
-        let (t, _) = remove_min_tree(self.0.force()).ok()?;
+        let (t, _) = remove_min_tree::<T, C>(self.0.force()).ok()?;
         Some(t.val)
     }
     pub fn delete_min(&self) -> Result<Self> {
+        if self.is_empty() {
+            bail!("empty");
+        }
         let this = self.clone();
-        Ok(Self(lazy!({
-            let (t, ts) = remove_min_tree(this.0.force()).unwrap();
-            merge(t.children.reverse(), ts)
-        })))
+        Ok(Self(
+            lazy!({
+                let (t, ts) = remove_min_tree::<T, C>(this.0.force()).unwrap();
+                merge::<T, C>(t.children.reverse(), ts)
+            }),
+            self.1 - 1,
+            PhantomData,
+        ))
     }
 }
 
-fn link<T: Clone + PartialOrd>(t1: Tree<T>, t2: Tree<T>) -> Tree<T> {
-    if t1.val <= t2.val {
+fn link<T: Clone, C: Compare<T>>(t1: Tree<T>, t2: Tree<T>) -> Tree<T> {
+    if C::le(&t1.val, &t2.val) {
         Tree {
             rank: t1.rank + 1,
             val: t1.val,
@@ -68,42 +110,42 @@ fn link<T: Clone + PartialOrd>(t1: Tree<T>, t2: Tree<T>) -> Tree<T> {
     }
 }
 
-fn ins_tree<T: Clone + PartialOrd>(t: Tree<T>, ts: List<Tree<T>>) -> List<Tree<T>> {
+fn ins_tree<T: Clone, C: Compare<T>>(t: Tree<T>, ts: List<Tree<T>>) -> List<Tree<T>> {
     match &*ts {
         Cell::Nil => List::cons(t, List::empty()),
         Cell::Cons(tp, tsp) => {
             if t.rank <= tp.rank {
                 List::cons(t, ts)
             } else {
-                ins_tree(link(t, tp.clone()), tsp.clone())
+                ins_tree::<T, C>(link::<T, C>(t, tp.clone()), tsp.clone())
             }
         }
     }
 }
 
-fn merge<T: Clone + PartialOrd>(ts1: List<Tree<T>>, ts2: List<Tree<T>>) -> List<Tree<T>> {
+fn merge<T: Clone, C: Compare<T>>(ts1: List<Tree<T>>, ts2: List<Tree<T>>) -> List<Tree<T>> {
     match (&*ts1, &*ts2) {
         (_, Cell::Nil) => ts1,
         (Cell::Nil, _) => ts2,
         (Cell::Cons(t1, ts1p), Cell::Cons(t2, ts2p)) => match t1.rank.cmp(&t2.rank) {
-            Ordering::Less => List::cons(t1.clone(), merge(ts1p.clone(), ts2)),
-            Ordering::Greater => List::cons(t2.clone(), merge(ts1, ts2p.clone())),
-            Ordering::Equal => ins_tree(
-                link(t1.clone(), t2.clone()),
-                merge(ts1p.clone(), ts2p.clone()),
+            Ordering::Less => List::cons(t1.clone(), merge::<T, C>(ts1p.clone(), ts2)),
+            Ordering::Greater => List::cons(t2.clone(), merge::<T, C>(ts1, ts2p.clone())),
+            Ordering::Equal => ins_tree::<T, C>(
+                link::<T, C>(t1.clone(), t2.clone()),
+                merge::<T, C>(ts1p.clone(), ts2p.clone()),
             ),
         },
     }
 }
 
-fn remove_min_tree<T: Clone + PartialOrd>(ts: List<Tree<T>>) -> Result<(Tree<T>, List<Tree<T>>)> {
+fn remove_min_tree<T: Clone, C: Compare<T>>(ts: List<Tree<T>>) -> Result<(Tree<T>, List<Tree<T>>)> {
     match &*ts {
         Cell::Nil => bail!("empty"),
         Cell::Cons(t, ts) => match &**ts {
             Cell::Nil => Ok((t.clone(), List::empty())),
             Cell::Cons(..) => {
-                let (tp, tsp) = remove_min_tree(ts.clone())?;
-                if t.val <= tp.val {
+                let (tp, tsp) = remove_min_tree::<T, C>(ts.clone())?;
+                if C::le(&t.val, &tp.val) {
                     Ok((t.clone(), ts.clone()))
                 } else {
                     Ok((tp, List::cons(t.clone(), tsp)))
@@ -113,7 +155,11 @@ fn remove_min_tree<T: Clone + PartialOrd>(ts: List<Tree<T>>) -> Result<(Tree<T>,
     }
 }
 
-impl_heap!(LazyBinomialHeap);
+impl_heap!(LazyBinomialHeap, LazyBinomialHeapFamily, {
+    fn len(&self) -> usize {
+        LazyBinomialHeap::len(self)
+    }
+});
 
 #[cfg(test)]
 mod tests {
@@ -1,39 +1,127 @@
-use std::sync::Arc;
+use std::{
+    marker::PhantomData,
+    sync::{Arc, Mutex},
+};
 
 use anyhow::{bail, Result};
 
-use crate::{lazy, lazy::Lazy};
+use crate::ord::{Compare, Natural};
 
 use super::impl_heap;
 
 #[derive(Clone)]
-pub struct LazyPairingHeap<T>(Arc<Heap<T>>);
+pub struct LazyPairingHeap<T, C = Natural>(Arc<Heap<T, C>>, PhantomData<C>);
 
 #[derive(Clone)]
-enum Heap<T> {
+enum Heap<T, C> {
     Empty,
-    Node(T, LazyPairingHeap<T>, Lazy<LazyPairingHeap<T>>),
+    /// size, element, child and suspended merge of the rest
+    Node(usize, T, LazyPairingHeap<T, C>, Susp<T, C>),
 }
 
-impl<T> From<Heap<T>> for LazyPairingHeap<T> {
-    fn from(x: Heap<T>) -> Self {
-        Self(Arc::new(x))
+/// Suspended `a.merge(&b).merge(&m.force())` of `link`. It is kept as data rather than
+/// a closure so that a long chain of suspensions forcing each other can be forced and
+/// dropped with loops.
+#[derive(Clone)]
+struct Susp<T, C>(Arc<Mutex<SuspCell<T, C>>>);
+
+enum SuspCell<T, C> {
+    Pending(LazyPairingHeap<T, C>, LazyPairingHeap<T, C>, Susp<T, C>),
+    Done(LazyPairingHeap<T, C>),
+}
+
+impl<T: Clone + 'static, C: Compare<T>> Susp<T, C> {
+    fn done(h: LazyPairingHeap<T, C>) -> Self {
+        Self(Arc::new(Mutex::new(SuspCell::Done(h))))
+    }
+    fn pending(a: LazyPairingHeap<T, C>, b: LazyPairingHeap<T, C>, m: Self) -> Self {
+        Self(Arc::new(Mutex::new(SuspCell::Pending(a, b, m))))
+    }
+    /// heaps held by the chain of suspensions, without forcing it
+    fn heaps(&self, out: &mut Vec<LazyPairingHeap<T, C>>) {
+        let mut m = self.clone();
+        loop {
+            let next = match &*m.0.lock().unwrap() {
+                SuspCell::Pending(a, b, mp) => {
+                    out.push(a.clone());
+                    out.push(b.clone());
+                    mp.clone()
+                }
+                SuspCell::Done(h) => {
+                    out.push(h.clone());
+                    break;
+                }
+            };
+            m = next;
+        }
+    }
+    /// force the chain of pending suspensions from the innermost one
+    fn force(&self) -> LazyPairingHeap<T, C> {
+        let mut chain = vec![self.clone()];
+        loop {
+            let m = match &*chain.last().unwrap().0.lock().unwrap() {
+                SuspCell::Pending(_, _, m) => m.clone(),
+                SuspCell::Done(_) => break,
+            };
+            chain.push(m);
+        }
+        let mut h = LazyPairingHeap::new();
+        for s in chain.iter().rev() {
+            let mut cell = s.0.lock().unwrap();
+            h = match &*cell {
+                SuspCell::Pending(a, b, _) => a.merge(b).merge(&h),
+                SuspCell::Done(hp) => hp.clone(),
+            };
+            *cell = SuspCell::Done(h.clone());
+        }
+        h
+    }
+}
+
+impl<T, C> From<Heap<T, C>> for LazyPairingHeap<T, C> {
+    fn from(x: Heap<T, C>) -> Self {
+        Self(Arc::new(x), PhantomData)
     }
 }
 
-impl<T: Clone + PartialOrd + 'static> LazyPairingHeap<T> {
+impl<T: Clone + Ord + 'static> LazyPairingHeap<T> {
+    /// empty min-heap; use `Heap::empty` for other orders
     pub fn empty() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone + 'static, C: Compare<T>> LazyPairingHeap<T, C> {
+    fn new() -> Self {
         Heap::Empty.into()
     }
     pub fn is_empty(&self) -> bool {
         matches!(self.0.as_ref(), Heap::Empty)
     }
+    /// elements in no particular order, without draining
+    pub fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        let mut stack = vec![self.clone()];
+        std::iter::from_fn(move || loop {
+            if let Heap::Node(_, x, a, m) = stack.pop()?.0.as_ref() {
+                stack.push(a.clone());
+                m.heaps(&mut stack);
+                return Some(x.clone());
+            }
+        })
+    }
+    /// O(1)
+    pub fn len(&self) -> usize {
+        match self.0.as_ref() {
+            Heap::Empty => 0,
+            Heap::Node(n, ..) => *n,
+        }
+    }
     pub fn merge(&self, other: &Self) -> Self {
         match (self.0.as_ref(), other.0.as_ref()) {
             (Heap::Empty, _) => other.clone(),
             (_, Heap::Empty) => self.clone(),
-            (Heap::Node(x, ..), Heap::Node(y, ..)) => {
-                if x <= y {
+            (Heap::Node(_, x, ..), Heap::Node(_, y, ..)) => {
+                if C::le(x, y) {
                     link(self, other).into()
                 } else {
                     link(other, self).into()
@@ -42,38 +130,38 @@ impl<T: Clone + PartialOrd + 'static> LazyPairingHeap<T> {
         }
     }
     pub fn insert(&self, x: T) -> Self {
-        Self::from(Heap::Node(x, Self::empty(), lazy!(Self::empty()))).merge(self)
+        Self::from(Heap::Node(1, x, Self::new(), Susp::done(Self::new()))).merge(self)
     }
     pub fn find_min(&self) -> Option<T> {
         match self.0.as_ref() {
             Heap::Empty => None,
-            Heap::Node(x, ..) => Some(x.clone()),
+            Heap::Node(_, x, ..) => Some(x.clone()),
         }
     }
     pub fn delete_min(&self) -> Result<Self> {
         match self.0.as_ref() {
             Heap::Empty => bail!("empty"),
-            Heap::Node(_, a, b) => Ok(a.merge(&b.force())),
+            Heap::Node(_, _, a, b) => Ok(a.merge(&b.force())),
         }
     }
 }
 
-fn link<T: Clone + PartialOrd + 'static>(
-    b: &LazyPairingHeap<T>,
-    a: &LazyPairingHeap<T>,
-) -> Heap<T> {
+fn link<T: Clone + 'static, C: Compare<T>>(
+    b: &LazyPairingHeap<T, C>,
+    a: &LazyPairingHeap<T, C>,
+) -> Heap<T, C> {
     let a = a.clone();
     match b.0.as_ref() {
-        Heap::Node(x, b, m) => {
+        Heap::Node(n, x, b, m) => {
+            let n = n + a.len();
             if b.is_empty() {
-                Heap::Node(x.clone(), a, m.clone())
+                Heap::Node(n, x.clone(), a, m.clone())
             } else {
-                let b = b.clone();
-                let m = m.clone();
                 Heap::Node(
+                    n,
                     x.clone(),
-                    LazyPairingHeap::empty(),
-                    lazy!(a.merge(&b).merge(&m.force())),
+                    LazyPairingHeap::new(),
+                    Susp::pending(a, b.clone(), m.clone()),
                 )
             }
         }
@@ -81,7 +169,51 @@ fn link<T: Clone + PartialOrd + 'static>(
     }
 }
 
-impl_heap!(LazyPairingHeap);
+impl<T, C> LazyPairingHeap<T, C> {
+    /// move out the subheaps, including those held by unshared suspensions, if no other
+    /// heap shares this node, leaving it empty
+    fn take_children(&mut self, out: &mut Vec<Self>) {
+        let Some(t) = Arc::get_mut(&mut self.0) else {
+            return;
+        };
+        let Heap::Node(_, _, a, m) = std::mem::replace(t, Heap::Empty) else {
+            return;
+        };
+        out.push(a);
+        let mut m = m;
+        while let Some(cell) = Arc::into_inner(m.0).and_then(|c| c.into_inner().ok()) {
+            match cell {
+                SuspCell::Pending(a, b, mp) => {
+                    out.push(a);
+                    out.push(b);
+                    m = mp;
+                }
+                SuspCell::Done(h) => {
+                    out.push(h);
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// drop unshared subheaps and suspensions with an explicit stack, as both nest as deep
+/// as the number of inserts
+impl<T, C> Drop for LazyPairingHeap<T, C> {
+    fn drop(&mut self) {
+        let mut stack = vec![];
+        self.take_children(&mut stack);
+        while let Some(mut h) = stack.pop() {
+            h.take_children(&mut stack);
+        }
+    }
+}
+
+impl_heap!(LazyPairingHeap, LazyPairingHeapFamily, {
+    fn len(&self) -> usize {
+        LazyPairingHeap::len(self)
+    }
+});
 
 #[cfg(test)]
 mod tests {
@@ -105,4 +237,18 @@ mod tests {
         let h = h.delete_min().unwrap();
         assert!(h.find_min().is_none());
     }
+
+    #[test]
+    fn test_million() {
+        let n = 1_000_000;
+        for xs in [(0..n).collect::<Vec<_>>(), (0..n).rev().collect()] {
+            let mut h = xs
+                .into_iter()
+                .fold(LazyPairingHeap::empty(), |h, x| h.insert(x));
+            for x in 0..10 {
+                assert_eq!(h.find_min(), Some(x));
+                h = h.delete_min().unwrap();
+            }
+        }
+    }
 }
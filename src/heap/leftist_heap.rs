@@ -1,41 +1,62 @@
-use std::sync::Arc;
+use std::{marker::PhantomData, sync::Arc};
 
 use anyhow::{bail, Result};
 
-use super::impl_heap;
+use crate::ord::{Compare, Natural};
+
+use super::{impl_heap, sorted_by_roots};
 
 #[derive(Clone)]
-pub struct LeftistHeap<T>(Arc<Tree<T>>);
+pub struct LeftistHeap<T, C = Natural>(Arc<Tree<T, C>>, PhantomData<C>);
 
 #[derive(Clone)]
-enum Tree<T> {
+enum Tree<T, C> {
     Empty,
-    Node(usize, T, LeftistHeap<T>, LeftistHeap<T>),
+    /// rank, size, element, left and right
+    Node(usize, usize, T, LeftistHeap<T, C>, LeftistHeap<T, C>),
 }
 
-impl<T> From<Tree<T>> for LeftistHeap<T> {
-    fn from(x: Tree<T>) -> Self {
-        Self(Arc::new(x))
+impl<T, C> From<Tree<T, C>> for LeftistHeap<T, C> {
+    fn from(x: Tree<T, C>) -> Self {
+        Self(Arc::new(x), PhantomData)
     }
 }
 
-impl<T: Clone + PartialOrd> LeftistHeap<T> {
+impl<T: Clone + Ord> LeftistHeap<T> {
+    /// empty min-heap; use `Heap::empty` for other orders
     pub fn empty() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone, C: Compare<T>> LeftistHeap<T, C> {
+    fn new() -> Self {
         Tree::Empty.into()
     }
     pub fn is_empty(&self) -> bool {
         matches!(self.0.as_ref(), Tree::Empty)
     }
+    /// elements in no particular order, without draining
+    pub fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        let mut stack = vec![self];
+        std::iter::from_fn(move || loop {
+            if let Tree::Node(_, _, x, a, b) = stack.pop()?.0.as_ref() {
+                stack.push(a);
+                stack.push(b);
+                return Some(x.clone());
+            }
+        })
+    }
     pub fn insert(&self, val: T) -> Self {
-        let e = Self::empty();
+        let e = Self::new();
         self.merge(&Self::make_node(val, e.clone(), e))
     }
     pub fn merge(&self, other: &Self) -> Self {
         match (self.0.as_ref(), other.0.as_ref()) {
             (_, Tree::Empty) => self.clone(),
             (Tree::Empty, _) => other.clone(),
-            (Tree::Node(_, x, a1, b1), Tree::Node(_, y, a2, b2)) => {
-                if x <= y {
+            (Tree::Node(_, _, x, a1, b1), Tree::Node(_, _, y, a2, b2)) => {
+                if C::le(x, y) {
                     Self::make_node(x.clone(), a1.clone(), b1.merge(other))
                 } else {
                     Self::make_node(y.clone(), a2.clone(), self.merge(b2))
@@ -46,13 +67,13 @@ impl<T: Clone + PartialOrd> LeftistHeap<T> {
     pub fn find_min(&self) -> Option<T> {
         match self.0.as_ref() {
             Tree::Empty => None,
-            Tree::Node(_, x, _, _) => Some(x.clone()),
+            Tree::Node(_, _, x, _, _) => Some(x.clone()),
         }
     }
     pub fn delete_min(&self) -> Result<Self> {
         match self.0.as_ref() {
             Tree::Empty => bail!("Empty"),
-            Tree::Node(_, _, a, b) => Ok(a.merge(b)),
+            Tree::Node(_, _, _, a, b) => Ok(a.merge(b)),
         }
     }
 
@@ -62,20 +83,28 @@ impl<T: Clone + PartialOrd> LeftistHeap<T> {
             Tree::Node(r, ..) => *r,
         }
     }
+    /// O(1)
+    pub fn len(&self) -> usize {
+        match self.0.as_ref() {
+            Tree::Empty => 0,
+            Tree::Node(_, n, ..) => *n,
+        }
+    }
     fn make_node(x: T, a: Self, b: Self) -> Self {
+        let n = a.len() + b.len() + 1;
         if a.rank() >= b.rank() {
-            Tree::Node(b.rank() + 1, x, a, b).into()
+            Tree::Node(b.rank() + 1, n, x, a, b).into()
         } else {
-            Tree::Node(a.rank() + 1, x, b, a).into()
+            Tree::Node(a.rank() + 1, n, x, b, a).into()
         }
     }
     /// exercise 3.2
     pub fn insert2(&self, x: T) -> Self {
-        let e = Self::empty();
+        let e = Self::new();
         match self.0.as_ref() {
             Tree::Empty => Self::make_node(x, e.clone(), e),
-            Tree::Node(_, y, a, b) => {
-                if &x <= y {
+            Tree::Node(_, _, y, a, b) => {
+                if C::le(&x, y) {
                     Self::make_node(x, a.clone().insert2(y.clone()), b.clone())
                 } else {
                     Self::make_node(y.clone(), a.clone().insert2(x), b.clone())
@@ -85,7 +114,25 @@ impl<T: Clone + PartialOrd> LeftistHeap<T> {
     }
 }
 
-impl_heap!(LeftistHeap);
+impl_heap!(LeftistHeap, LeftistHeapFamily, {
+    fn len(&self) -> usize {
+        LeftistHeap::len(self)
+    }
+    /// O(n log n) by walking the tree, see `sorted_by_roots`
+    fn into_sorted_vec(self) -> Vec<T> {
+        sorted_by_roots::<_, _, C>(
+            [self.0.as_ref()],
+            |t| match t {
+                Tree::Node(_, _, x, _, _) => Some(x),
+                Tree::Empty => None,
+            },
+            |t| match t {
+                Tree::Node(_, _, _, a, b) => vec![a.0.as_ref(), b.0.as_ref()],
+                Tree::Empty => vec![],
+            },
+        )
+    }
+});
 
 #[cfg(test)]
 mod tests {
@@ -132,4 +179,23 @@ mod tests {
         let h = h.delete_min().unwrap();
         assert_eq!(h.find_min(), Some(4));
     }
+
+    #[test]
+    fn test_len() {
+        let h = [5, 3, 8, 1]
+            .into_iter()
+            .fold(LeftistHeap::empty(), |h, x| h.insert2(x));
+        assert_eq!(h.len(), 4);
+        assert_eq!(h.merge(&h).len(), 8);
+        assert_eq!(h.delete_min().unwrap().len(), 3);
+        assert_eq!(LeftistHeap::<i32>::empty().len(), 0);
+    }
+
+    #[test]
+    fn test_heapsort() {
+        let xs = List::cons(3, List::cons(1, List::cons(2, List::empty())));
+        let ys = LeftistHeap::<_>::heapsort(xs);
+        assert_eq!(ys.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert!(LeftistHeap::<i32>::heapsort(List::empty()).is_empty());
+    }
 }
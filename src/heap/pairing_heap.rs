@@ -1,69 +1,161 @@
 use anyhow::{bail, Result};
 
-use crate::{stack::Cell, List};
-use std::sync::Arc;
+use crate::{
+    ord::{Compare, Natural},
+    stack::Cell,
+    List,
+};
+use std::{marker::PhantomData, sync::Arc};
 
 use super::impl_heap;
 
 #[derive(Clone)]
-pub struct PairingHeap<T>(Arc<Tree<T>>);
+pub struct PairingHeap<T, C = Natural>(Arc<Tree<T, C>>, PhantomData<C>);
 
 #[derive(Clone)]
-enum Tree<T> {
+enum Tree<T, C> {
     Empty,
-    Node(T, List<PairingHeap<T>>),
+    /// size, element and subheaps
+    Node(usize, T, List<PairingHeap<T, C>>),
 }
 
-impl<T: Clone + PartialOrd> PairingHeap<T> {
+impl<T: Clone + Ord> PairingHeap<T> {
+    /// empty min-heap; use `Heap::empty` for other orders
     pub fn empty() -> Self {
-        Self(Arc::new(Tree::Empty))
+        Self::new()
+    }
+}
+
+impl<T: Clone, C: Compare<T>> PairingHeap<T, C> {
+    fn new() -> Self {
+        Self(Arc::new(Tree::Empty), PhantomData)
     }
     pub fn is_empty(&self) -> bool {
         matches!(self.0.as_ref(), Tree::Empty)
     }
+    /// elements in no particular order, without draining
+    pub fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        let mut stack = vec![self];
+        std::iter::from_fn(move || loop {
+            if let Tree::Node(_, x, hs) = stack.pop()?.0.as_ref() {
+                stack.extend(hs.iter());
+                return Some(x.clone());
+            }
+        })
+    }
     pub fn find_min(&self) -> Option<T> {
         match self.0.as_ref() {
             Tree::Empty => None,
-            Tree::Node(x, _) => Some(x.clone()),
+            Tree::Node(_, x, _) => Some(x.clone()),
+        }
+    }
+    /// O(1)
+    pub fn len(&self) -> usize {
+        match self.0.as_ref() {
+            Tree::Empty => 0,
+            Tree::Node(n, ..) => *n,
         }
     }
-    fn node(x: T, hs: List<Self>) -> Self {
-        Self(Arc::new(Tree::Node(x, hs)))
+    fn node(n: usize, x: T, hs: List<Self>) -> Self {
+        Self(Arc::new(Tree::Node(n, x, hs)), PhantomData)
     }
     pub fn merge(&self, other: &Self) -> Self {
         match (self.0.as_ref(), other.0.as_ref()) {
             (Tree::Empty, _) => other.clone(),
             (_, Tree::Empty) => self.clone(),
-            (Tree::Node(x, hs1), Tree::Node(y, hs2)) => {
-                if x <= y {
-                    Self::node(x.clone(), List::cons(other.clone(), hs1.clone()))
+            (Tree::Node(n1, x, hs1), Tree::Node(n2, y, hs2)) => {
+                if C::le(x, y) {
+                    Self::node(n1 + n2, x.clone(), List::cons(other.clone(), hs1.clone()))
                 } else {
-                    Self::node(y.clone(), List::cons(self.clone(), hs2.clone()))
+                    Self::node(n1 + n2, y.clone(), List::cons(self.clone(), hs2.clone()))
                 }
             }
         }
     }
     pub fn insert(&self, x: T) -> Self {
-        self.merge(&Self::node(x, List::empty()))
+        self.merge(&Self::node(1, x, List::empty()))
     }
+    /// merge pairs from the left, then merge the results from the right, with loops
+    /// instead of recursion over the possibly long list of children
     fn merge_pairs(hs: List<Self>) -> Self {
-        match &*hs {
-            Cell::Nil => Self::empty(),
-            Cell::Cons(h1, hs) => match &**hs {
-                Cell::Nil => h1.clone(),
-                Cell::Cons(h2, hs) => h1.merge(h2).merge(&Self::merge_pairs(hs.clone())),
-            },
+        let mut pairs = vec![];
+        let mut hs = hs;
+        while let Cell::Cons(h1, hsp) = &*hs {
+            let next = match &**hsp {
+                Cell::Nil => {
+                    pairs.push(h1.clone());
+                    break;
+                }
+                Cell::Cons(h2, hspp) => {
+                    pairs.push(h1.merge(h2));
+                    hspp.clone()
+                }
+            };
+            hs = next;
         }
+        pairs
+            .into_iter()
+            .rev()
+            .fold(Self::new(), |h, hp| hp.merge(&h))
     }
     pub fn delete_min(&self) -> Result<Self> {
         match self.0.as_ref() {
             Tree::Empty => bail!("empty"),
-            Tree::Node(_, hs) => Ok(Self::merge_pairs(hs.clone())),
+            Tree::Node(_, _, hs) => Ok(Self::merge_pairs(hs.clone())),
         }
     }
 }
 
-impl_heap!(PairingHeap);
+impl<T, C> PairingHeap<T, C> {
+    /// move out the subheaps if no other heap shares this node, leaving it empty
+    fn take_children(&mut self, out: &mut Vec<Self>) {
+        if let Some(t) = Arc::get_mut(&mut self.0) {
+            if let Tree::Node(_, _, mut hs) = std::mem::replace(t, Tree::Empty) {
+                while let Some((h, tail)) = hs.take_unique() {
+                    out.push(h);
+                    hs = tail;
+                }
+            }
+        }
+    }
+}
+
+/// drop unshared subheaps with an explicit stack, as a heap built by descending inserts
+/// is a chain as deep as its size
+impl<T, C> Drop for PairingHeap<T, C> {
+    fn drop(&mut self) {
+        let mut stack = vec![];
+        self.take_children(&mut stack);
+        while let Some(mut h) = stack.pop() {
+            h.take_children(&mut stack);
+        }
+    }
+}
+
+impl_heap!(PairingHeap, PairingHeapFamily, {
+    fn len(&self) -> usize {
+        PairingHeap::len(self)
+    }
+    /// the minimum on top of a single list of singleton heaps, O(n)
+    fn from_iter<I: IntoIterator<Item = T>>(xs: I) -> Self {
+        let mut xs = xs.into_iter().collect::<Vec<_>>();
+        if xs.is_empty() {
+            return PairingHeap::new();
+        }
+        let mut i = 0;
+        for (j, x) in xs.iter().enumerate() {
+            if !C::le(&xs[i], x) {
+                i = j;
+            }
+        }
+        let n = xs.len();
+        let min = xs.swap_remove(i);
+        let hs = xs.into_iter().fold(List::empty(), |hs, x| {
+            List::cons(PairingHeap::node(1, x, List::empty()), hs)
+        });
+        PairingHeap::node(n, min, hs)
+    }
+});
 
 #[cfg(test)]
 mod tests {
@@ -87,4 +179,18 @@ mod tests {
         let h = h.delete_min().unwrap();
         assert!(h.find_min().is_none());
     }
+
+    #[test]
+    fn test_million() {
+        let n = 1_000_000;
+        for xs in [(0..n).collect::<Vec<_>>(), (0..n).rev().collect()] {
+            let mut h = xs
+                .into_iter()
+                .fold(PairingHeap::empty(), |h, x| h.insert(x));
+            for x in 0..10 {
+                assert_eq!(h.find_min(), Some(x));
+                h = h.delete_min().unwrap();
+            }
+        }
+    }
 }
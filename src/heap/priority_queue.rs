@@ -0,0 +1,179 @@
+use std::{cmp::Ordering, marker::PhantomData};
+
+use crate::ord::Natural;
+
+use super::{pairing_heap::PairingHeap, Heap};
+
+/// Element of a `PriorityQueue`, ordered by priority and then by insertion order
+#[derive(Clone)]
+pub struct Entry<P, V> {
+    priority: P,
+    seq: u64,
+    value: V,
+}
+
+impl<P: Ord, V> PartialEq for Entry<P, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<P: Ord, V> Eq for Entry<P, V> {}
+
+impl<P: Ord, V> PartialOrd for Entry<P, V> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<P: Ord, V> Ord for Entry<P, V> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority
+            .cmp(&other.priority)
+            .then(self.seq.cmp(&other.seq))
+    }
+}
+
+/// Min-priority queue of values popping ties in insertion order, on top of any heap.
+/// Use `std::cmp::Reverse` priorities for a max-priority queue.
+#[derive(Clone)]
+pub struct PriorityQueue<P, V, H = PairingHeap<Entry<P, V>>> {
+    seq: u64,
+    heap: H,
+    phantom: PhantomData<Entry<P, V>>,
+}
+
+impl<P: Clone + Ord + 'static, V: Clone + 'static> PriorityQueue<P, V> {
+    pub fn empty() -> Self {
+        Self::default()
+    }
+}
+
+impl<P, V, H> Default for PriorityQueue<P, V, H>
+where
+    P: Clone + Ord,
+    V: Clone,
+    H: Heap<Entry<P, V>, Comparator = Natural>,
+{
+    fn default() -> Self {
+        Self {
+            seq: 0,
+            heap: H::empty(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<P, V, H> PriorityQueue<P, V, H>
+where
+    P: Clone + Ord,
+    V: Clone,
+    H: Heap<Entry<P, V>, Comparator = Natural>,
+{
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+    pub fn push(&self, priority: P, value: V) -> Self {
+        let entry = Entry {
+            priority,
+            seq: self.seq,
+            value,
+        };
+        Self {
+            seq: self.seq + 1,
+            heap: self.heap.insert(entry),
+            phantom: PhantomData,
+        }
+    }
+    pub fn peek(&self) -> Option<(P, V)> {
+        let e = self.heap.find_min()?;
+        Some((e.priority, e.value))
+    }
+    pub fn pop(&self) -> Option<(P, V, Self)> {
+        let e = self.heap.find_min()?;
+        let heap = self.heap.delete_min().ok()?;
+        Some((
+            e.priority,
+            e.value,
+            Self {
+                seq: self.seq,
+                heap,
+                phantom: PhantomData,
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heap::{
+        binomial_heap::BinomialHeap, bootstrapped_heap::BootstrappedHeap,
+        lazy_binomial_heap::LazyBinomialHeap, lazy_pairing_heap::LazyPairingHeap,
+        leftist_heap::LeftistHeap, scheduled_binomial_heap::ScheduledBinomialHeap,
+        skew_binomial_heap::SkewBinomialHeap, splay_heap::SplayHeap,
+        weight_biased_leftist_heap::WeightBiasedLeftistHeap,
+    };
+
+    #[test]
+    fn test_fifo_ties() {
+        let jobs = [
+            (2, 'a'),
+            (1, 'b'),
+            (2, 'c'),
+            (1, 'd'),
+            (0, 'e'),
+            (2, 'f'),
+            (1, 'g'),
+        ];
+        let expected = vec![
+            (0, 'e'),
+            (1, 'b'),
+            (1, 'd'),
+            (1, 'g'),
+            (2, 'a'),
+            (2, 'c'),
+            (2, 'f'),
+        ];
+
+        macro_rules! check {
+            ($heap:ident) => {
+                let mut q = jobs
+                    .iter()
+                    .fold(PriorityQueue::<_, _, $heap<_>>::default(), |q, &(p, v)| {
+                        q.push(p, v)
+                    });
+                assert_eq!(q.peek(), Some((0, 'e')));
+                let mut popped = vec![];
+                while let Some((p, v, rest)) = q.pop() {
+                    popped.push((p, v));
+                    q = rest;
+                }
+                assert!(q.is_empty());
+                assert_eq!(popped, expected);
+            };
+        }
+        check!(LeftistHeap);
+        check!(WeightBiasedLeftistHeap);
+        check!(BinomialHeap);
+        check!(PairingHeap);
+        check!(SplayHeap);
+        check!(LazyBinomialHeap);
+        check!(LazyPairingHeap);
+        check!(ScheduledBinomialHeap);
+        check!(SkewBinomialHeap);
+        check!(BootstrappedHeap);
+    }
+
+    #[test]
+    fn test_persistent() {
+        let q = PriorityQueue::empty().push(1, 'a').push(1, 'b');
+        let (_, _, q1) = q.pop().unwrap();
+        let q2 = q1.push(0, 'c');
+
+        assert_eq!(q.peek(), Some((1, 'a')));
+        assert_eq!(q1.peek(), Some((1, 'b')));
+        assert_eq!(q2.peek(), Some((0, 'c')));
+        assert!(q1.pop().unwrap().2.is_empty());
+    }
+}
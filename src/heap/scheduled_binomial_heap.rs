@@ -1,16 +1,25 @@
+use std::marker::PhantomData;
+
 use anyhow::{bail, Result};
 
 use crate::{
     lazy,
     lazy::{Stream, StreamCell},
+    ord::{Compare, Natural},
     stack::Cell,
     List,
 };
 
 use super::impl_heap;
 
+/// digits, their schedule and the number of elements
 #[derive(Clone)]
-pub struct ScheduledBinomialHeap<T>(Stream<Digit<T>>, Schedule<T>);
+pub struct ScheduledBinomialHeap<T, C = Natural>(
+    Stream<Digit<T>>,
+    Schedule<T>,
+    usize,
+    PhantomData<C>,
+);
 
 #[derive(Clone)]
 struct Schedule<T>(List<Stream<Digit<T>>>);
@@ -24,69 +33,111 @@ enum Digit<T> {
 #[derive(Clone)]
 struct Tree<T>(T, List<Tree<T>>);
 
-impl<T: Clone + PartialOrd + 'static> ScheduledBinomialHeap<T> {
+impl<T: Clone + Ord + 'static> ScheduledBinomialHeap<T> {
+    /// empty min-heap; use `Heap::empty` for other orders
     pub fn empty() -> Self {
-        Self(Stream::empty(), Schedule(List::empty()))
+        Self::new()
+    }
+}
+
+impl<T: Clone + 'static, C: Compare<T>> ScheduledBinomialHeap<T, C> {
+    fn new() -> Self {
+        Self(Stream::empty(), Schedule(List::empty()), 0, PhantomData)
     }
     pub fn is_empty(&self) -> bool {
-        self.0.is_empty().force()
+        self.2 == 0
+    }
+    /// O(1)
+    pub fn len(&self) -> usize {
+        self.2
+    }
+    /// elements in no particular order, without draining
+    pub fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        let mut stack = vec![];
+        let mut ds = self.0.clone();
+        while let StreamCell::Cons(d, dsp) = ds.force() {
+            if let Digit::One(t) = d {
+                stack.push(t);
+            }
+            ds = dsp;
+        }
+        std::iter::from_fn(move || {
+            let Tree(x, ts) = stack.pop()?;
+            stack.extend(ts.iter().cloned());
+            Some(x)
+        })
     }
     pub fn insert(&self, x: T) -> Self {
-        let dsp = ins_tree(Tree(x, List::empty()), self.0.clone());
+        let dsp = ins_tree::<T, C>(Tree(x, List::empty()), self.0.clone());
         Self(
             dsp.clone(),
             Schedule(List::cons(dsp, self.1 .0.clone())).exec().exec(),
+            self.2 + 1,
+            PhantomData,
         )
     }
     pub fn merge(&self, other: &Self) -> Self {
-        let ds = normalize(merge(self.0.clone(), other.0.clone()));
-        Self(ds, Schedule(List::empty()))
+        let ds = normalize(merge::<T, C>(self.0.clone(), other.0.clone()));
+        Self(ds, Schedule(List::empty()), self.2 + other.2, PhantomData)
     }
     pub fn find_min(&self) -> Option<T> {
-        remove_min_tree(self.0.clone()).ok().map(|(t, _)| t.0)
+        remove_min_tree::<T, C>(self.0.clone())
+            .ok()
+            .map(|(t, _)| t.0)
     }
     pub fn delete_min(&self) -> Result<Self> {
-        let (t, ds) = remove_min_tree(self.0.clone())?;
-        let dsp = merge(t.1.reverse().map(Digit::One).to_stream(), ds);
-        Ok(Self(normalize(dsp), Schedule(List::empty())))
+        let (t, ds) = remove_min_tree::<T, C>(self.0.clone())?;
+        let dsp = merge::<T, C>(t.1.reverse().map(Digit::One).to_stream(), ds);
+        Ok(Self(
+            normalize(dsp),
+            Schedule(List::empty()),
+            self.2 - 1,
+            PhantomData,
+        ))
     }
 }
 
-fn link<T: Clone + PartialOrd>(t1: Tree<T>, t2: Tree<T>) -> Tree<T> {
-    if t1.0 <= t2.0 {
+fn link<T: Clone, C: Compare<T>>(t1: Tree<T>, t2: Tree<T>) -> Tree<T> {
+    if C::le(&t1.0, &t2.0) {
         Tree(t1.0.clone(), List::cons(t2, t1.1))
     } else {
         Tree(t2.0.clone(), List::cons(t1, t2.1))
     }
 }
 
-fn ins_tree<T: Clone + PartialOrd + 'static>(t: Tree<T>, ds: Stream<Digit<T>>) -> Stream<Digit<T>> {
+fn ins_tree<T: Clone + 'static, C: Compare<T>>(
+    t: Tree<T>,
+    ds: Stream<Digit<T>>,
+) -> Stream<Digit<T>> {
     match ds.force() {
         StreamCell::Nil => Stream::cons(lazy!((Digit::One(t), Stream::empty()))),
         StreamCell::Cons(d, ds) => match d {
             Digit::Zero => Stream::cons(lazy!((Digit::One(t), ds))),
-            Digit::One(tp) => Stream::cons(lazy!((Digit::Zero, ins_tree(link(t, tp), ds)))),
+            Digit::One(tp) => Stream::cons(lazy!((
+                Digit::Zero,
+                ins_tree::<T, C>(link::<T, C>(t, tp), ds)
+            ))),
         },
     }
 }
 
-fn merge<T: Clone + PartialOrd + 'static>(
+fn merge<T: Clone + 'static, C: Compare<T>>(
     ds1: Stream<Digit<T>>,
     ds2: Stream<Digit<T>>,
 ) -> Stream<Digit<T>> {
     match (ds1.force(), ds2.force()) {
-        (StreamCell::Nil, _) => ds1,
-        (_, StreamCell::Nil) => ds2,
+        (StreamCell::Nil, _) => ds2,
+        (_, StreamCell::Nil) => ds1,
         (StreamCell::Cons(Digit::Zero, ds1), StreamCell::Cons(d, ds2)) => {
-            Stream::cons(lazy!((d, merge(ds1, ds2))))
+            Stream::cons(lazy!((d, merge::<T, C>(ds1, ds2))))
         }
         (StreamCell::Cons(d, ds1), StreamCell::Cons(Digit::Zero, ds2)) => {
-            Stream::cons(lazy!((d, merge(ds1, ds2))))
+            Stream::cons(lazy!((d, merge::<T, C>(ds1, ds2))))
         }
         (StreamCell::Cons(Digit::One(t1), ds1), StreamCell::Cons(Digit::One(t2), ds2)) => {
             Stream::cons(lazy!((
                 Digit::Zero,
-                ins_tree(link(t1, t2), merge(ds1, ds2))
+                ins_tree::<T, C>(link::<T, C>(t1, t2), merge::<T, C>(ds1, ds2))
             )))
         }
     }
@@ -111,19 +162,19 @@ impl<T: Clone> Schedule<T> {
     }
 }
 
-fn remove_min_tree<T: Clone + PartialOrd + 'static>(
+fn remove_min_tree<T: Clone + 'static, C: Compare<T>>(
     ds: Stream<Digit<T>>,
 ) -> Result<(Tree<T>, Stream<Digit<T>>)> {
     match ds.force() {
         StreamCell::Nil => bail!("empty"),
         StreamCell::Cons(Digit::One(t), ds) if ds.is_empty().force() => Ok((t, Stream::empty())),
         StreamCell::Cons(Digit::Zero, ds) => {
-            let (tp, dsp) = remove_min_tree(ds)?;
+            let (tp, dsp) = remove_min_tree::<T, C>(ds)?;
             Ok((tp, Stream::cons(lazy!((Digit::Zero, dsp)))))
         }
         StreamCell::Cons(Digit::One(t), ds) => {
-            let (tp, dsp) = remove_min_tree(ds.clone())?;
-            if t.0 <= tp.0 {
+            let (tp, dsp) = remove_min_tree::<T, C>(ds.clone())?;
+            if C::le(&t.0, &tp.0) {
                 Ok((t, Stream::cons(lazy!((Digit::Zero, ds)))))
             } else {
                 Ok((tp, Stream::cons(lazy!((Digit::One(t), dsp)))))
@@ -132,7 +183,11 @@ fn remove_min_tree<T: Clone + PartialOrd + 'static>(
     }
 }
 
-impl_heap!(ScheduledBinomialHeap);
+impl_heap!(ScheduledBinomialHeap, ScheduledBinomialHeapFamily, {
+    fn len(&self) -> usize {
+        ScheduledBinomialHeap::len(self)
+    }
+});
 
 #[cfg(test)]
 mod tests {
@@ -156,4 +211,19 @@ mod tests {
         let h = h.delete_min().unwrap();
         assert!(h.find_min().is_none());
     }
+
+    #[test]
+    fn test_merge_empty() {
+        // merging with no digits left on either side keeps the other side
+        let h = [5, 6, 1]
+            .into_iter()
+            .fold(ScheduledBinomialHeap::empty(), |h, x| h.insert(x));
+        let e = ScheduledBinomialHeap::empty();
+        assert_eq!(e.merge(&h).find_min(), Some(1));
+        assert_eq!(h.merge(&e).find_min(), Some(1));
+        // the minimum is a lone tree without children, merged into the rest
+        let h = h.delete_min().unwrap();
+        assert_eq!(h.find_min(), Some(5));
+        assert_eq!(h.iter().count(), 2);
+    }
 }
@@ -0,0 +1,244 @@
+use anyhow::{bail, Result};
+use std::{cmp::Ordering, marker::PhantomData, sync::Arc};
+
+use crate::{
+    ord::{Compare, Natural},
+    stack::Cell,
+    List,
+};
+
+use super::impl_heap;
+
+struct Tree<T> {
+    rank: usize,
+    elem: T,
+    /// elements absorbed by skew links
+    elems: List<T>,
+    children: Trees<T>,
+}
+
+type Trees<T> = List<Arc<Tree<T>>>;
+
+impl<T: Clone> Tree<T> {
+    fn node(rank: usize, elem: T, elems: List<T>, children: Trees<T>) -> Arc<Tree<T>> {
+        Arc::new(Tree {
+            rank,
+            elem,
+            elems,
+            children,
+        })
+    }
+    fn link<C: Compare<T>>(self: Arc<Self>, other: Arc<Self>) -> Arc<Tree<T>> {
+        if C::le(&self.elem, &other.elem) {
+            Self::node(
+                self.rank + 1,
+                self.elem.clone(),
+                self.elems.clone(),
+                List::cons(other, self.children.clone()),
+            )
+        } else {
+            Self::node(
+                self.rank + 1,
+                other.elem.clone(),
+                other.elems.clone(),
+                List::cons(self, other.children.clone()),
+            )
+        }
+    }
+    fn skew_link<C: Compare<T>>(x: T, t1: Arc<Self>, t2: Arc<Self>) -> Arc<Tree<T>> {
+        let t = t1.link::<C>(t2);
+        if C::le(&x, &t.elem) {
+            Self::node(
+                t.rank,
+                x,
+                List::cons(t.elem.clone(), t.elems.clone()),
+                t.children.clone(),
+            )
+        } else {
+            Self::node(
+                t.rank,
+                t.elem.clone(),
+                List::cons(x, t.elems.clone()),
+                t.children.clone(),
+            )
+        }
+    }
+}
+
+/// Skew binomial heap (section 9.3.2), whose trees follow the skew binary numbers
+/// of `SkewBinaryRandomAccessList`. Only the first two trees may have the same rank.
+#[derive(Clone)]
+pub struct SkewBinomialHeap<T, C = Natural>(Trees<T>, usize, PhantomData<C>);
+
+impl<T: Clone + Ord> SkewBinomialHeap<T> {
+    /// empty min-heap; use `Heap::empty` for other orders
+    pub fn empty() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone, C: Compare<T>> SkewBinomialHeap<T, C> {
+    fn new() -> Self {
+        Self::from_trees(List::empty(), 0)
+    }
+    /// trees holding `n` elements
+    fn from_trees(ts: Trees<T>, n: usize) -> Self {
+        Self(ts, n, PhantomData)
+    }
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    /// O(1)
+    pub fn len(&self) -> usize {
+        self.1
+    }
+    /// elements in no particular order, without draining
+    pub fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        let mut stack = self.0.iter().collect::<Vec<_>>();
+        std::iter::from_fn(move || {
+            let t = stack.pop()?;
+            stack.extend(t.children.iter());
+            Some(t)
+        })
+        .flat_map(|t| std::iter::once(t.elem.clone()).chain(t.elems.iter().cloned()))
+    }
+    fn ins_tree(t: Arc<Tree<T>>, ts: Trees<T>) -> Trees<T> {
+        match &*ts {
+            Cell::Nil => List::cons(t, List::empty()),
+            Cell::Cons(tp, tsp) => {
+                if t.rank < tp.rank {
+                    List::cons(t, ts.clone())
+                } else {
+                    Self::ins_tree(t.link::<C>(tp.clone()), tsp.clone())
+                }
+            }
+        }
+    }
+    fn merge_trees(ts1: Trees<T>, ts2: Trees<T>) -> Trees<T> {
+        match (&*ts1, &*ts2) {
+            (_, Cell::Nil) => ts1,
+            (Cell::Nil, _) => ts2,
+            (Cell::Cons(t1, ts1p), Cell::Cons(t2, ts2p)) => match t1.rank.cmp(&t2.rank) {
+                Ordering::Less => List::cons(t1.clone(), Self::merge_trees(ts1p.clone(), ts2)),
+                Ordering::Greater => List::cons(t2.clone(), Self::merge_trees(ts1, ts2p.clone())),
+                Ordering::Equal => Self::ins_tree(
+                    t1.clone().link::<C>(t2.clone()),
+                    Self::merge_trees(ts1p.clone(), ts2p.clone()),
+                ),
+            },
+        }
+    }
+    /// remove the leading pair of trees with the same rank
+    fn normalize(ts: Trees<T>) -> Trees<T> {
+        match &*ts {
+            Cell::Nil => ts,
+            Cell::Cons(t, ts) => Self::ins_tree(t.clone(), ts.clone()),
+        }
+    }
+    /// O(1) worst case
+    pub fn insert(&self, x: T) -> Self {
+        if let Cell::Cons(t1, ts) = &*self.0 {
+            if let Cell::Cons(t2, rest) = &**ts {
+                if t1.rank == t2.rank {
+                    return Self::from_trees(
+                        List::cons(
+                            Tree::skew_link::<C>(x, t1.clone(), t2.clone()),
+                            rest.clone(),
+                        ),
+                        self.1 + 1,
+                    );
+                }
+            }
+        }
+        Self::from_trees(
+            List::cons(
+                Tree::node(0, x, List::empty(), List::empty()),
+                self.0.clone(),
+            ),
+            self.1 + 1,
+        )
+    }
+    pub fn merge(&self, other: &Self) -> Self {
+        Self::from_trees(
+            Self::merge_trees(
+                Self::normalize(self.0.clone()),
+                Self::normalize(other.0.clone()),
+            ),
+            self.1 + other.1,
+        )
+    }
+    fn remove_min_tree(ts: &Trees<T>) -> Result<(Arc<Tree<T>>, Trees<T>)> {
+        match &**ts {
+            Cell::Nil => bail!("empty"),
+            Cell::Cons(t, tsp) if tsp.is_empty() => Ok((t.clone(), List::empty())),
+            Cell::Cons(t, tsp) => {
+                let (tp, tspp) = Self::remove_min_tree(tsp)?;
+                if C::le(&t.elem, &tp.elem) {
+                    Ok((t.clone(), tsp.clone()))
+                } else {
+                    Ok((tp, List::cons(t.clone(), tspp)))
+                }
+            }
+        }
+    }
+    pub fn find_min(&self) -> Option<T> {
+        Self::remove_min_tree(&self.0)
+            .ok()
+            .map(|(t, _)| t.elem.clone())
+    }
+    pub fn delete_min(&self) -> Result<Self> {
+        let (t, ts) = Self::remove_min_tree(&self.0)?;
+        // only the size of the whole is known, not those of the parts
+        let mut h = Self::from_trees(t.children.reverse(), 0).merge(&Self::from_trees(ts, 0));
+        let mut xs = t.elems.clone();
+        while let Cell::Cons(x, xsp) = &*xs.clone() {
+            h = h.insert(x.clone());
+            xs = xsp.clone();
+        }
+        Ok(Self::from_trees(h.0, self.1 - 1))
+    }
+}
+
+impl_heap!(SkewBinomialHeap, SkewBinomialHeapFamily, {
+    fn len(&self) -> usize {
+        SkewBinomialHeap::len(self)
+    }
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test() {
+        let h = [3, 1, 4, 0, 2, 1]
+            .into_iter()
+            .fold(SkewBinomialHeap::empty(), |h, x| h.insert(x));
+
+        let mut xs = vec![];
+        let mut h = h;
+        while let Some(x) = h.find_min() {
+            xs.push(x);
+            h = h.delete_min().unwrap();
+        }
+        assert_eq!(xs, vec![0, 1, 1, 2, 3, 4]);
+        assert!(h.delete_min().is_err());
+    }
+
+    #[test]
+    fn test_merge() {
+        let h1 = (0..20)
+            .step_by(2)
+            .fold(SkewBinomialHeap::empty(), |h, x| h.insert(x));
+        let h2 = (1..20)
+            .step_by(2)
+            .fold(SkewBinomialHeap::empty(), |h, x| h.insert(x));
+
+        let mut h = h1.merge(&h2);
+        for x in 0..20 {
+            assert_eq!(h.find_min(), Some(x));
+            h = h.delete_min().unwrap();
+        }
+        assert!(h.is_empty());
+    }
+}
@@ -1,52 +1,81 @@
-use std::sync::Arc;
+use std::{marker::PhantomData, sync::Arc};
 
 use anyhow::{bail, Result};
 
+use crate::ord::{Compare, Natural};
+
 use super::impl_heap;
 
 #[derive(Clone)]
-enum Tree<T> {
+enum Tree<T, C> {
     Empty,
-    Node(T, SplayHeap<T>, SplayHeap<T>),
+    /// size, element and children
+    Node(usize, T, SplayHeap<T, C>, SplayHeap<T, C>),
 }
 
 #[derive(Clone)]
-pub struct SplayHeap<T>(Arc<Tree<T>>);
+pub struct SplayHeap<T, C = Natural>(Arc<Tree<T, C>>, PhantomData<C>);
 
-impl<T> From<Tree<T>> for SplayHeap<T> {
-    fn from(x: Tree<T>) -> Self {
-        Self(Arc::new(x))
+impl<T, C> From<Tree<T, C>> for SplayHeap<T, C> {
+    fn from(x: Tree<T, C>) -> Self {
+        Self(Arc::new(x), PhantomData)
     }
 }
 
-impl<T> From<Arc<Tree<T>>> for SplayHeap<T> {
-    fn from(x: Arc<Tree<T>>) -> Self {
-        Self(x)
+impl<T, C> From<Arc<Tree<T, C>>> for SplayHeap<T, C> {
+    fn from(x: Arc<Tree<T, C>>) -> Self {
+        Self(x, PhantomData)
     }
 }
 
-impl<T: Clone + PartialOrd> SplayHeap<T> {
+impl<T: Clone + Ord> SplayHeap<T> {
+    /// empty min-heap; use `Heap::empty` for other orders
     pub fn empty() -> Self {
-        Self(Arc::new(Tree::Empty))
+        Self::new()
+    }
+}
+
+impl<T: Clone, C: Compare<T>> SplayHeap<T, C> {
+    fn new() -> Self {
+        Tree::Empty.into()
     }
 
     pub fn is_empty(&self) -> bool {
         matches!(self.0.as_ref(), Tree::Empty)
     }
+    /// elements in no particular order, without draining
+    pub fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        let mut stack = vec![self];
+        std::iter::from_fn(move || loop {
+            if let Tree::Node(_, x, a, b) = stack.pop()?.0.as_ref() {
+                stack.push(a);
+                stack.push(b);
+                return Some(x.clone());
+            }
+        })
+    }
+
+    /// O(1)
+    pub fn len(&self) -> usize {
+        match self.0.as_ref() {
+            Tree::Empty => 0,
+            Tree::Node(n, ..) => *n,
+        }
+    }
 
     fn node(x: T, a: Self, b: Self) -> Self {
-        Self(Arc::new(Tree::Node(x, a, b)))
+        Tree::Node(1 + a.len() + b.len(), x, a, b).into()
     }
 
     fn partition(&self, pivot: T) -> (Self, Self) {
         match self.0.as_ref() {
-            Tree::Empty => (Self::empty(), Self::empty()),
-            Tree::Node(x, a, b) => {
-                if x <= &pivot {
+            Tree::Empty => (Self::new(), Self::new()),
+            Tree::Node(_, x, a, b) => {
+                if C::le(x, &pivot) {
                     match b.0.as_ref() {
-                        Tree::Empty => (self.clone(), Self::empty()),
-                        Tree::Node(y, b1, b2) => {
-                            if y <= &pivot {
+                        Tree::Empty => (self.clone(), Self::new()),
+                        Tree::Node(_, y, b1, b2) => {
+                            if C::le(y, &pivot) {
                                 let (small, big) = b2.partition(pivot);
                                 (
                                     Self::node(
@@ -67,9 +96,9 @@ impl<T: Clone + PartialOrd> SplayHeap<T> {
                     }
                 } else {
                     match a.0.as_ref() {
-                        Tree::Empty => (Self::empty(), self.clone()),
-                        Tree::Node(y, a1, a2) => {
-                            if y <= &pivot {
+                        Tree::Empty => (Self::new(), self.clone()),
+                        Tree::Node(_, y, a1, a2) => {
+                            if C::le(y, &pivot) {
                                 let (small, big) = a2.partition(pivot);
                                 (
                                     Self::node(y.clone(), a1.clone(), small),
@@ -101,7 +130,7 @@ impl<T: Clone + PartialOrd> SplayHeap<T> {
     pub fn merge(&self, other: &Self) -> Self {
         match self.0.as_ref() {
             Tree::Empty => other.clone(),
-            Tree::Node(x, a, b) => {
+            Tree::Node(_, x, a, b) => {
                 let (ta, tb) = other.partition(x.clone());
                 Self::node(x.clone(), ta.merge(a), tb.merge(b))
             }
@@ -111,7 +140,7 @@ impl<T: Clone + PartialOrd> SplayHeap<T> {
     pub fn find_min(&self) -> Option<T> {
         match self.0.as_ref() {
             Tree::Empty => None,
-            Tree::Node(x, a, _) => match a.0.as_ref() {
+            Tree::Node(_, x, a, _) => match a.0.as_ref() {
                 Tree::Empty => Some(x.clone()),
                 _ => a.find_min(),
             },
@@ -121,9 +150,9 @@ impl<T: Clone + PartialOrd> SplayHeap<T> {
     pub fn delete_min(&self) -> Result<Self> {
         match self.0.as_ref() {
             Tree::Empty => bail!("empty"),
-            Tree::Node(y, a, c) => Ok(match a.0.as_ref() {
+            Tree::Node(_, y, a, c) => Ok(match a.0.as_ref() {
                 Tree::Empty => c.clone(),
-                Tree::Node(x, a, b) => match a.0.as_ref() {
+                Tree::Node(_, x, a, b) => match a.0.as_ref() {
                     Tree::Empty => Self::node(y.clone(), b.clone(), c.clone()),
                     Tree::Node(..) => Self::node(
                         x.clone(),
@@ -136,7 +165,11 @@ impl<T: Clone + PartialOrd> SplayHeap<T> {
     }
 }
 
-impl_heap!(SplayHeap);
+impl_heap!(SplayHeap, SplayHeapFamily, {
+    fn len(&self) -> usize {
+        SplayHeap::len(self)
+    }
+});
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,203 @@
+use std::{marker::PhantomData, sync::Arc};
+
+use anyhow::{bail, Result};
+
+use crate::ord::{Compare, Natural};
+
+use super::impl_heap;
+
+/// Weight-biased leftist heap (exercise 3.4), whose left child is never smaller than
+/// the right one.
+#[derive(Clone)]
+pub struct WeightBiasedLeftistHeap<T, C = Natural>(Arc<Tree<T, C>>, PhantomData<C>);
+
+#[derive(Clone)]
+enum Tree<T, C> {
+    Empty,
+    /// size, element, left and right
+    Node(
+        usize,
+        T,
+        WeightBiasedLeftistHeap<T, C>,
+        WeightBiasedLeftistHeap<T, C>,
+    ),
+}
+
+impl<T, C> From<Tree<T, C>> for WeightBiasedLeftistHeap<T, C> {
+    fn from(x: Tree<T, C>) -> Self {
+        Self(Arc::new(x), PhantomData)
+    }
+}
+
+impl<T: Clone + Ord> WeightBiasedLeftistHeap<T> {
+    /// empty min-heap; use `Heap::empty` for other orders
+    pub fn empty() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone, C: Compare<T>> WeightBiasedLeftistHeap<T, C> {
+    fn new() -> Self {
+        Tree::Empty.into()
+    }
+    pub fn is_empty(&self) -> bool {
+        matches!(self.0.as_ref(), Tree::Empty)
+    }
+    /// elements in no particular order, without draining
+    pub fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        let mut stack = vec![self];
+        std::iter::from_fn(move || loop {
+            if let Tree::Node(_, x, a, b) = stack.pop()?.0.as_ref() {
+                stack.push(a);
+                stack.push(b);
+                return Some(x.clone());
+            }
+        })
+    }
+    /// O(1)
+    pub fn len(&self) -> usize {
+        match self.0.as_ref() {
+            Tree::Empty => 0,
+            Tree::Node(n, ..) => *n,
+        }
+    }
+    pub fn insert(&self, x: T) -> Self {
+        let e = Self::new();
+        self.merge(&Tree::Node(1, x, e.clone(), e).into())
+    }
+    /// exercise 3.4 (c), single top-down pass: the size of the merged subtree is
+    /// known before merging, so the nodes on the path are arranged on the way down and
+    /// linked bottom-up from an explicit stack instead of recursion
+    pub fn merge(&self, other: &Self) -> Self {
+        // (size, element, untouched child, whether the merged child goes left)
+        let mut path = vec![];
+        let mut h1 = self.clone();
+        let mut h2 = other.clone();
+        let mut h = loop {
+            match (h1.0.as_ref(), h2.0.as_ref()) {
+                (_, Tree::Empty) => break h1,
+                (Tree::Empty, _) => break h2,
+                (Tree::Node(n1, x, a1, b1), Tree::Node(n2, y, a2, b2)) => {
+                    let n = n1 + n2;
+                    let (x, a, b, rest) = if C::le(x, y) {
+                        (x, a1, b1, h2.clone())
+                    } else {
+                        (y, a2, b2, h1.clone())
+                    };
+                    path.push((n, x.clone(), a.clone(), a.len() < b.len() + rest.len()));
+                    (h1, h2) = (b.clone(), rest);
+                }
+            }
+        };
+        while let Some((n, x, a, left)) = path.pop() {
+            h = if left {
+                Tree::Node(n, x, h, a).into()
+            } else {
+                Tree::Node(n, x, a, h).into()
+            };
+        }
+        h
+    }
+    pub fn find_min(&self) -> Option<T> {
+        match self.0.as_ref() {
+            Tree::Empty => None,
+            Tree::Node(_, x, _, _) => Some(x.clone()),
+        }
+    }
+    pub fn delete_min(&self) -> Result<Self> {
+        match self.0.as_ref() {
+            Tree::Empty => bail!("Empty"),
+            Tree::Node(_, _, a, b) => Ok(a.merge(b)),
+        }
+    }
+}
+
+impl<T, C> WeightBiasedLeftistHeap<T, C> {
+    /// move out the children if no other heap shares this node, leaving it empty
+    fn take_children(&mut self, out: &mut Vec<Self>) {
+        if let Some(t) = Arc::get_mut(&mut self.0) {
+            if let Tree::Node(_, _, a, b) = std::mem::replace(t, Tree::Empty) {
+                out.push(a);
+                out.push(b);
+            }
+        }
+    }
+}
+
+/// drop unshared subheaps with an explicit stack, as a heap built by descending inserts
+/// has a left path as deep as its size
+impl<T, C> Drop for WeightBiasedLeftistHeap<T, C> {
+    fn drop(&mut self) {
+        let mut stack = vec![];
+        self.take_children(&mut stack);
+        while let Some(mut h) = stack.pop() {
+            h.take_children(&mut stack);
+        }
+    }
+}
+
+impl_heap!(WeightBiasedLeftistHeap, WeightBiasedLeftistHeapFamily, {
+    fn len(&self) -> usize {
+        WeightBiasedLeftistHeap::len(self)
+    }
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heap::Heap;
+
+    fn check_invariant<T: Clone, C: Compare<T>>(h: &WeightBiasedLeftistHeap<T, C>) {
+        if let Tree::Node(n, x, a, b) = h.0.as_ref() {
+            assert_eq!(*n, a.len() + b.len() + 1);
+            assert!(a.len() >= b.len());
+            for c in [a, b] {
+                if let Some(y) = c.find_min() {
+                    assert!(C::le(x, &y));
+                }
+                check_invariant(c);
+            }
+        }
+    }
+
+    #[test]
+    fn test() {
+        let h = [5, 2, 9, 2, 7, 0, 4]
+            .into_iter()
+            .fold(WeightBiasedLeftistHeap::empty(), |h, x| h.insert(x));
+        check_invariant(&h);
+        assert_eq!(h.len(), 7);
+        assert_eq!(h.into_sorted_vec(), vec![0, 2, 2, 4, 5, 7, 9]);
+    }
+
+    #[test]
+    fn test_merge() {
+        let h1 = (0..50)
+            .step_by(3)
+            .fold(WeightBiasedLeftistHeap::empty(), |h, x| h.insert(x));
+        let h2 = (0..50)
+            .filter(|x| x % 3 != 0)
+            .fold(WeightBiasedLeftistHeap::empty(), |h, x| {
+                h.merge(&WeightBiasedLeftistHeap::empty().insert(x))
+            });
+        let h = h1.merge(&h2);
+        check_invariant(&h2);
+        check_invariant(&h);
+        assert_eq!(h2.len(), 33);
+        assert_eq!(h.len(), 50);
+        assert_eq!(h.into_sorted_vec(), (0..50).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_large() {
+        // descending inserts build a left path as deep as the heap
+        let h = (0..1_000_000)
+            .rev()
+            .fold(WeightBiasedLeftistHeap::empty(), |h, x| h.insert(x));
+        assert_eq!(h.find_min(), Some(0));
+        assert_eq!(h.len(), 1_000_000);
+        let h = h.delete_min().unwrap();
+        assert_eq!(h.find_min(), Some(1));
+        drop(h);
+    }
+}
@@ -0,0 +1,332 @@
+use std::sync::Arc;
+
+use crate::tree::FiniteMap;
+
+/// Big-endian Patricia tree (Okasaki & Gill, "Fast Mergeable Integer Maps")
+#[derive(Clone)]
+pub struct IntMap<V>(Arc<Node<V>>);
+
+enum Node<V> {
+    Empty,
+    Leaf(u64, V),
+    /// prefix, branching bit, subtree with the bit cleared, subtree with the bit set
+    Branch(u64, u64, IntMap<V>, IntMap<V>),
+}
+
+impl<V> From<Node<V>> for IntMap<V> {
+    fn from(x: Node<V>) -> Self {
+        Self(Arc::new(x))
+    }
+}
+
+/// upper bits of `k` above the branching bit `m`
+fn mask(k: u64, m: u64) -> u64 {
+    k & !(m | (m - 1))
+}
+
+fn match_prefix(k: u64, p: u64, m: u64) -> bool {
+    mask(k, m) == p
+}
+
+fn zero(k: u64, m: u64) -> bool {
+    k & m == 0
+}
+
+/// highest bit at which `p1` and `p2` differ
+fn branching_bit(p1: u64, p2: u64) -> u64 {
+    1 << (63 - (p1 ^ p2).leading_zeros())
+}
+
+impl<V: Clone> IntMap<V> {
+    pub fn empty() -> Self {
+        Node::Empty.into()
+    }
+    pub fn is_empty(&self) -> bool {
+        matches!(self.0.as_ref(), Node::Empty)
+    }
+    fn leaf(k: u64, x: V) -> Self {
+        Node::Leaf(k, x).into()
+    }
+    /// branch node which collapses empty subtrees
+    fn branch(p: u64, m: u64, t0: Self, t1: Self) -> Self {
+        if t0.is_empty() {
+            t1
+        } else if t1.is_empty() {
+            t0
+        } else {
+            Node::Branch(p, m, t0, t1).into()
+        }
+    }
+    fn join(p1: u64, t1: Self, p2: u64, t2: Self) -> Self {
+        let m = branching_bit(p1, p2);
+        if zero(p1, m) {
+            Node::Branch(mask(p1, m), m, t1, t2).into()
+        } else {
+            Node::Branch(mask(p1, m), m, t2, t1).into()
+        }
+    }
+    pub fn lookup(&self, k: u64) -> Option<V> {
+        match self.0.as_ref() {
+            Node::Empty => None,
+            Node::Leaf(j, x) if *j == k => Some(x.clone()),
+            Node::Leaf(..) => None,
+            Node::Branch(p, m, _, _) if !match_prefix(k, *p, *m) => None,
+            Node::Branch(_, m, t0, t1) => {
+                if zero(k, *m) {
+                    t0.lookup(k)
+                } else {
+                    t1.lookup(k)
+                }
+            }
+        }
+    }
+    pub fn member(&self, k: u64) -> bool {
+        self.lookup(k).is_some()
+    }
+    /// insert `x` at `k`, combining with an existing value by `f(new, old)`
+    pub fn insert_with<F: Fn(V, V) -> V>(&self, f: &F, k: u64, x: V) -> Self {
+        match self.0.as_ref() {
+            Node::Empty => Self::leaf(k, x),
+            Node::Leaf(j, y) if *j == k => Self::leaf(k, f(x, y.clone())),
+            Node::Leaf(j, _) => Self::join(k, Self::leaf(k, x), *j, self.clone()),
+            Node::Branch(p, m, t0, t1) => {
+                if !match_prefix(k, *p, *m) {
+                    Self::join(k, Self::leaf(k, x), *p, self.clone())
+                } else if zero(k, *m) {
+                    Node::Branch(*p, *m, t0.insert_with(f, k, x), t1.clone()).into()
+                } else {
+                    Node::Branch(*p, *m, t0.clone(), t1.insert_with(f, k, x)).into()
+                }
+            }
+        }
+    }
+    pub fn insert(&self, k: u64, x: V) -> Self {
+        self.insert_with(&|x, _| x, k, x)
+    }
+    pub fn remove(&self, k: u64) -> Self {
+        match self.0.as_ref() {
+            Node::Empty => self.clone(),
+            Node::Leaf(j, _) if *j == k => Self::empty(),
+            Node::Leaf(..) => self.clone(),
+            Node::Branch(p, m, _, _) if !match_prefix(k, *p, *m) => self.clone(),
+            Node::Branch(p, m, t0, t1) => {
+                if zero(k, *m) {
+                    Self::branch(*p, *m, t0.remove(k), t1.clone())
+                } else {
+                    Self::branch(*p, *m, t0.clone(), t1.remove(k))
+                }
+            }
+        }
+    }
+    /// left-biased union: values of `self` win on common keys
+    pub fn union(&self, other: &Self) -> Self {
+        match (self.0.as_ref(), other.0.as_ref()) {
+            (Node::Empty, _) => other.clone(),
+            (_, Node::Empty) => self.clone(),
+            (Node::Leaf(k, x), _) => other.insert(*k, x.clone()),
+            (_, Node::Leaf(k, x)) => self.insert_with(&|_, y| y, *k, x.clone()),
+            (Node::Branch(p, m, s0, s1), Node::Branch(q, n, t0, t1)) => {
+                if m == n && p == q {
+                    Node::Branch(*p, *m, s0.union(t0), s1.union(t1)).into()
+                } else if m > n && match_prefix(*q, *p, *m) {
+                    if zero(*q, *m) {
+                        Node::Branch(*p, *m, s0.union(other), s1.clone()).into()
+                    } else {
+                        Node::Branch(*p, *m, s0.clone(), s1.union(other)).into()
+                    }
+                } else if m < n && match_prefix(*p, *q, *n) {
+                    if zero(*p, *n) {
+                        Node::Branch(*q, *n, self.union(t0), t1.clone()).into()
+                    } else {
+                        Node::Branch(*q, *n, t0.clone(), self.union(t1)).into()
+                    }
+                } else {
+                    Self::join(*p, self.clone(), *q, other.clone())
+                }
+            }
+        }
+    }
+    /// left-biased intersection: values are taken from `self`
+    pub fn intersection(&self, other: &Self) -> Self {
+        match (self.0.as_ref(), other.0.as_ref()) {
+            (Node::Empty, _) | (_, Node::Empty) => Self::empty(),
+            (Node::Leaf(k, x), _) => match other.lookup(*k) {
+                Some(_) => Self::leaf(*k, x.clone()),
+                None => Self::empty(),
+            },
+            (_, Node::Leaf(k, _)) => match self.lookup(*k) {
+                Some(x) => Self::leaf(*k, x),
+                None => Self::empty(),
+            },
+            (Node::Branch(p, m, s0, s1), Node::Branch(q, n, t0, t1)) => {
+                if m == n {
+                    if p == q {
+                        Self::branch(*p, *m, s0.intersection(t0), s1.intersection(t1))
+                    } else {
+                        Self::empty()
+                    }
+                } else if m > n {
+                    if !match_prefix(*q, *p, *m) {
+                        Self::empty()
+                    } else if zero(*q, *m) {
+                        s0.intersection(other)
+                    } else {
+                        s1.intersection(other)
+                    }
+                } else if !match_prefix(*p, *q, *n) {
+                    Self::empty()
+                } else if zero(*p, *n) {
+                    self.intersection(t0)
+                } else {
+                    self.intersection(t1)
+                }
+            }
+        }
+    }
+    pub fn size(&self) -> usize {
+        match self.0.as_ref() {
+            Node::Empty => 0,
+            Node::Leaf(..) => 1,
+            Node::Branch(_, _, t0, t1) => t0.size() + t1.size(),
+        }
+    }
+    /// iterate entries in ascending order of keys
+    pub fn iter(&self) -> Iter<V> {
+        Iter(vec![self.clone()])
+    }
+}
+
+pub struct Iter<V>(Vec<IntMap<V>>);
+
+impl<V: Clone> Iterator for Iter<V> {
+    type Item = (u64, V);
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(t) = self.0.pop() {
+            match t.0.as_ref() {
+                Node::Empty => {}
+                Node::Leaf(k, x) => return Some((*k, x.clone())),
+                Node::Branch(_, _, t0, t1) => {
+                    self.0.push(t1.clone());
+                    self.0.push(t0.clone());
+                }
+            }
+        }
+        None
+    }
+}
+
+impl<V: Clone> FiniteMap<u64, V> for IntMap<V> {
+    fn empty() -> Self {
+        IntMap::empty()
+    }
+    fn bind(&self, key: u64, value: V) -> Self {
+        self.insert(key, value)
+    }
+    fn lookup(&self, key: u64) -> Option<V> {
+        IntMap::lookup(self, key)
+    }
+}
+
+#[derive(Clone)]
+pub struct IntSet(IntMap<()>);
+
+impl IntSet {
+    pub fn empty() -> Self {
+        Self(IntMap::empty())
+    }
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    pub fn insert(&self, k: u64) -> Self {
+        Self(self.0.insert(k, ()))
+    }
+    pub fn member(&self, k: u64) -> bool {
+        self.0.member(k)
+    }
+    pub fn remove(&self, k: u64) -> Self {
+        Self(self.0.remove(k))
+    }
+    pub fn union(&self, other: &Self) -> Self {
+        Self(self.0.union(&other.0))
+    }
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self(self.0.intersection(&other.0))
+    }
+    pub fn size(&self) -> usize {
+        self.0.size()
+    }
+    pub fn iter(&self) -> impl Iterator<Item = u64> {
+        self.0.iter().map(|(k, _)| k)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map() {
+        let m = [(5, "five"), (1, "one"), (u64::MAX, "max"), (4, "four")]
+            .into_iter()
+            .fold(IntMap::empty(), |m, (k, v)| m.insert(k, v));
+
+        assert_eq!(m.lookup(1), Some("one"));
+        assert_eq!(m.lookup(4), Some("four"));
+        assert_eq!(m.lookup(u64::MAX), Some("max"));
+        assert_eq!(m.lookup(0), None);
+        assert_eq!(m.size(), 4);
+
+        let m = m.insert(4, "FOUR").remove(5);
+        assert_eq!(m.lookup(4), Some("FOUR"));
+        assert_eq!(m.lookup(5), None);
+        assert_eq!(
+            m.iter().collect::<Vec<_>>(),
+            vec![(1, "one"), (4, "FOUR"), (u64::MAX, "max")]
+        );
+    }
+
+    #[test]
+    fn test_union_intersection() {
+        let s = (0..100)
+            .step_by(2)
+            .fold(IntMap::empty(), |m, k| m.insert(k, 's'));
+        let t = (0..100)
+            .step_by(3)
+            .fold(IntMap::empty(), |m, k| m.insert(k, 't'));
+
+        let u = s.union(&t);
+        assert_eq!(
+            u.iter().collect::<Vec<_>>(),
+            (0..100)
+                .filter(|k| k % 2 == 0 || k % 3 == 0)
+                .map(|k| (k, if k % 2 == 0 { 's' } else { 't' }))
+                .collect::<Vec<_>>()
+        );
+
+        let i = t.intersection(&s);
+        assert_eq!(
+            i.iter().collect::<Vec<_>>(),
+            (0..100).step_by(6).map(|k| (k, 't')).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_set() {
+        let s = [3, 1, 4, 1, 5]
+            .into_iter()
+            .fold(IntSet::empty(), |s, x| s.insert(x));
+        let t = [2, 7, 1, 8]
+            .into_iter()
+            .fold(IntSet::empty(), |s, x| s.insert(x));
+
+        assert!(s.member(4));
+        assert!(!s.member(2));
+        assert_eq!(s.size(), 4);
+        assert_eq!(
+            s.union(&t).iter().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 5, 7, 8]
+        );
+        assert_eq!(s.intersection(&t).iter().collect::<Vec<_>>(), vec![1]);
+        assert!(s.remove(1).remove(3).remove(4).remove(5).is_empty());
+    }
+}
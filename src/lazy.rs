@@ -1,22 +1,18 @@
+use anyhow::{bail, Result};
 use std::{
     ops::Deref,
     sync::{Arc, Mutex},
 };
 
+#[derive(Default)]
 enum LazyCell<T> {
     Future(Box<dyn FnOnce() -> T>),
     Done(T),
     /// default value only for std::mem::take
+    #[default]
     None,
 }
 
-// implment manually because derive impl required T to be default
-impl<T> Default for LazyCell<T> {
-    fn default() -> Self {
-        Self::None
-    }
-}
-
 #[derive(Clone)]
 pub struct Lazy<T>(Arc<Mutex<LazyCell<T>>>);
 
@@ -39,6 +35,20 @@ impl<T: Clone> Lazy<T> {
     }
 }
 
+impl<T> Lazy<T> {
+    /// already evaluated suspension
+    pub fn value(x: T) -> Self {
+        Self(Arc::new(Mutex::new(LazyCell::Done(x))))
+    }
+    /// move out the evaluated value if no other suspension shares it
+    pub(crate) fn into_value(self) -> Option<T> {
+        match Arc::into_inner(self.0)?.into_inner().ok()? {
+            LazyCell::Done(x) => Some(x),
+            _ => None,
+        }
+    }
+}
+
 #[macro_export]
 macro_rules! lazy {
     ($e:expr) => {
@@ -62,6 +72,30 @@ impl<T> Deref for Stream<T> {
 #[derive(Clone)]
 pub struct Stream<T>(Lazy<StreamCell<T>>);
 
+/// unlinks the evaluated cells no other stream shares one by one instead of recursively
+impl<T> Drop for Stream<T> {
+    fn drop(&mut self) {
+        let mut next = self.take_tail();
+        while let Some(mut s) = next {
+            next = s.take_tail();
+        }
+    }
+}
+
+impl<T> Stream<T> {
+    /// the rest of an evaluated cons cell held only by `self`, leaving the cell empty
+    fn take_tail(&mut self) -> Option<Stream<T>> {
+        let cell = Arc::get_mut(&mut (self.0).0)?.get_mut().ok()?;
+        match std::mem::take(cell) {
+            LazyCell::Done(StreamCell::Cons(_, xs)) => Some(xs),
+            other => {
+                *cell = other;
+                None
+            }
+        }
+    }
+}
+
 impl<T: Clone + 'static> Stream<T> {
     pub fn empty() -> Self {
         Self(lazy!(StreamCell::Nil))
@@ -87,6 +121,42 @@ impl<T: Clone + 'static> Stream<T> {
         })
     }
 
+    /// iterator forcing the cells one by one
+    pub fn iter(&self) -> impl Iterator<Item = T> {
+        let mut s = self.clone();
+        std::iter::from_fn(move || match s.0.force() {
+            StreamCell::Nil => None,
+            StreamCell::Cons(x, xs) => {
+                s = xs;
+                Some(x)
+            }
+        })
+    }
+
+    /// replace the `i`-th element, evaluating the cells before it
+    pub fn update(&self, i: usize, x: T) -> Result<Self> {
+        let mut prefix = vec![];
+        let mut s = self.clone();
+        for _ in 0..i {
+            match s.0.force() {
+                StreamCell::Nil => bail!("invalid index"),
+                StreamCell::Cons(y, ys) => {
+                    prefix.push(y);
+                    s = ys;
+                }
+            }
+        }
+        let StreamCell::Cons(_, rest) = s.0.force() else {
+            bail!("invalid index")
+        };
+        Ok(prefix
+            .into_iter()
+            .rev()
+            .fold(Stream::cons(lazy!((x, rest))), |s, y| {
+                Stream::cons(lazy!((y, s)))
+            }))
+    }
+
     pub fn tail(&self) -> Self {
         let this = self.to_owned();
         Self(lazy!(match this.0.force() {
@@ -115,23 +185,27 @@ impl<T: Clone + 'static> Stream<T> {
 
     pub fn drop(&self, n: usize) -> Self {
         let this = self.clone();
-        Self(lazy!(match (this.0.force(), n) {
-            (s, 0) => s,
-            (StreamCell::Nil, _) => StreamCell::Nil,
-            (StreamCell::Cons(_, xs), n) => xs.drop(n - 1).0.force(),
+        Self(lazy!({
+            let mut s = this;
+            for _ in 0..n {
+                match s.0.force() {
+                    StreamCell::Nil => return StreamCell::Nil,
+                    StreamCell::Cons(_, xs) => s = xs,
+                }
+            }
+            s.0.force()
         }))
     }
 
     pub fn reverse(&self) -> Self {
         let this = self.clone();
         Self(lazy!({
-            fn rev<T: Clone + 'static>(xs: Stream<T>, ys: Stream<T>) -> Stream<T> {
-                match xs.0.force() {
-                    StreamCell::Nil => ys,
-                    StreamCell::Cons(x, xs) => rev(xs, Stream::cons(lazy!((x, ys)))),
-                }
+            let (mut xs, mut ys) = (this, Stream::empty());
+            while let StreamCell::Cons(x, rest) = xs.0.force() {
+                ys = Stream(Lazy::value(StreamCell::Cons(x, ys)));
+                xs = rest;
             }
-            rev(this, Stream::empty()).0.force()
+            ys.0.force()
         }))
     }
 }
@@ -1,8 +1,14 @@
+pub mod catenable_list;
 pub mod deque;
+pub mod hamt;
 pub mod heap;
+pub mod int_map;
 pub mod lazy;
+pub mod ord;
+pub mod priority_search_queue;
 pub mod queue;
 pub mod random_access_list;
+pub mod rebuild;
 pub mod red_black_tree;
 pub mod stack;
 pub mod tree;
@@ -0,0 +1,187 @@
+use std::{cmp::Ordering, marker::PhantomData};
+
+/// Order of elements in a heap. The order is given as a type rather than a value
+/// so that heaps ordered differently have different types and cannot be merged.
+pub trait Compare<T>: Clone + 'static {
+    fn compare(x: &T, y: &T) -> Ordering;
+    fn le(x: &T, y: &T) -> bool {
+        Self::compare(x, y) != Ordering::Greater
+    }
+}
+
+/// Ascending order by `Ord`, i.e. min-heap
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Natural;
+
+impl<T: Ord> Compare<T> for Natural {
+    fn compare(x: &T, y: &T) -> Ordering {
+        x.cmp(y)
+    }
+}
+
+/// Descending order by `Ord`, i.e. max-heap
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Reverse;
+
+impl<T: Ord> Compare<T> for Reverse {
+    fn compare(x: &T, y: &T) -> Ordering {
+        y.cmp(x)
+    }
+}
+
+/// Key extracted from an element, used by `ByKey`
+pub trait Key<T>: 'static {
+    type Output: Ord;
+    fn key(x: &T) -> Self::Output;
+}
+
+/// Ascending order of the key given by `K`
+#[derive(Debug)]
+pub struct ByKey<K>(PhantomData<K>);
+
+// implement manually because derive impl requires K to be Clone
+impl<K> Clone for ByKey<K> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K> Copy for ByKey<K> {}
+
+impl<T, K: Key<T>> Compare<T> for ByKey<K> {
+    fn compare(x: &T, y: &T) -> Ordering {
+        K::key(x).cmp(&K::key(y))
+    }
+}
+
+/// Totally ordered wrapper for floating point numbers using IEEE 754 `totalOrder`,
+/// so that they can be stored in heaps and trees requiring `Ord`.
+/// Negative NaN is the smallest value and positive NaN is the largest.
+#[derive(Debug, Clone, Copy)]
+pub struct Total<T>(pub T);
+
+macro_rules! impl_total {
+    ($t:ty) => {
+        impl PartialEq for Total<$t> {
+            fn eq(&self, other: &Self) -> bool {
+                self.cmp(other) == Ordering::Equal
+            }
+        }
+
+        impl Eq for Total<$t> {}
+
+        impl PartialOrd for Total<$t> {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for Total<$t> {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.0.total_cmp(&other.0)
+            }
+        }
+    };
+}
+
+impl_total!(f32);
+impl_total!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        heap::{
+            binomial_heap::BinomialHeap, lazy_binomial_heap::LazyBinomialHeap,
+            lazy_pairing_heap::LazyPairingHeap, leftist_heap::LeftistHeap,
+            pairing_heap::PairingHeap, scheduled_binomial_heap::ScheduledBinomialHeap,
+            skew_binomial_heap::SkewBinomialHeap, splay_heap::SplayHeap,
+            weight_biased_leftist_heap::WeightBiasedLeftistHeap, Heap,
+        },
+        red_black_tree::RedBlackTree,
+        tree::{Tree, UnbalancedSet},
+    };
+
+    const XS: [f64; 7] = [3.0, f64::NAN, -1.0, f64::INFINITY, 0.5, f64::NAN, -2.0];
+
+    fn sorted<T: Clone, H: Heap<T>>(xs: &[T]) -> Vec<T> {
+        xs.iter()
+            .fold(H::empty(), |h, x| h.insert(x.clone()))
+            .into_sorted_vec()
+    }
+
+    struct Len;
+
+    impl Key<&'static str> for Len {
+        type Output = usize;
+        fn key(x: &&'static str) -> usize {
+            x.len()
+        }
+    }
+
+    #[test]
+    fn test_heap_orders() {
+        macro_rules! check {
+            ($heap:ident) => {
+                assert_eq!(
+                    sorted::<_, $heap<_, Reverse>>(&[3, 1, 4, 1, 5]),
+                    vec![5, 4, 3, 1, 1]
+                );
+                assert_eq!(
+                    sorted::<_, $heap<_, ByKey<Len>>>(&["ccc", "a", "bb"]),
+                    vec!["a", "bb", "ccc"]
+                );
+            };
+        }
+        check!(LeftistHeap);
+        check!(BinomialHeap);
+        check!(PairingHeap);
+        check!(SplayHeap);
+        check!(LazyBinomialHeap);
+        check!(LazyPairingHeap);
+        check!(ScheduledBinomialHeap);
+        check!(SkewBinomialHeap);
+        check!(WeightBiasedLeftistHeap);
+    }
+
+    fn check_sorted(ys: &[Total<f64>]) {
+        let ys = ys.iter().map(|y| y.0).collect::<Vec<_>>();
+        assert_eq!(ys.len(), XS.len());
+        assert_eq!(ys[..5], [-2.0, -1.0, 0.5, 3.0, f64::INFINITY]);
+        assert!(ys[5].is_nan() && ys[6].is_nan());
+    }
+
+    #[test]
+    fn test_heap_with_nan() {
+        let h = XS
+            .into_iter()
+            .fold(LeftistHeap::empty(), |h, x| h.insert(Total(x)));
+        check_sorted(&h.into_sorted_vec());
+
+        let h = XS
+            .into_iter()
+            .fold(PairingHeap::empty(), |h, x| h.insert(Total(x)));
+        check_sorted(&h.into_sorted_vec());
+    }
+
+    #[test]
+    fn test_tree_with_nan() {
+        let s = XS
+            .into_iter()
+            .fold(RedBlackTree::empty(), |s, x| s.insert(Total(x)));
+        for x in XS {
+            assert!(s.member(Total(x)));
+        }
+        assert!(!s.member(Total(1.0)));
+
+        let t = XS
+            .into_iter()
+            .fold(<Tree<_> as UnbalancedSet<_>>::empty(), |t, x| {
+                t.insert(Total(x))
+            });
+        for x in XS {
+            assert!(t.member(&Total(x)));
+        }
+        assert!(!t.member(&Total(1.0)));
+    }
+}
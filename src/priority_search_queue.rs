@@ -0,0 +1,436 @@
+use std::{cmp::Ordering, sync::Arc};
+
+use anyhow::{bail, Result};
+
+/// Priority search queue as a semi-heap tournament (Hinze, "A Simple Implementation
+/// Technique for Priority Search Queues"). Bindings are ordered by key like a search
+/// tree and by priority like a heap at once.
+#[derive(Clone)]
+pub struct PrioritySearchQueue<K, P>(Option<Winner<K, P>>);
+
+/// winner of the tournament, the losers and the largest key
+#[derive(Clone)]
+struct Winner<K, P>(K, P, LTree<K, P>, K);
+
+/// weight-balanced tree of the losers of each match, split by key
+#[derive(Clone)]
+struct LTree<K, P>(Arc<Node<K, P>>);
+
+enum Node<K, P> {
+    Start,
+    /// size, loser, left subtree, split key and right subtree;
+    /// the loser comes from the left iff its key is at most the split key
+    Loser(usize, K, P, LTree<K, P>, K, LTree<K, P>),
+}
+
+/// the tournament split into its last match
+enum View<K, P> {
+    Null,
+    Single(K, P),
+    Play(PrioritySearchQueue<K, P>, PrioritySearchQueue<K, P>),
+}
+
+/// weight balance factor
+const OMEGA: usize = 4;
+
+impl<K: Clone + Ord, P: Clone + Ord> LTree<K, P> {
+    fn start() -> Self {
+        Self(Arc::new(Node::Start))
+    }
+    fn size(&self) -> usize {
+        match self.0.as_ref() {
+            Node::Start => 0,
+            Node::Loser(s, ..) => *s,
+        }
+    }
+    fn loser(k: K, p: P, l: Self, m: K, r: Self) -> Self {
+        Self(Arc::new(Node::Loser(
+            1 + l.size() + r.size(),
+            k,
+            p,
+            l,
+            m,
+            r,
+        )))
+    }
+    fn balance(k: K, p: P, l: Self, m: K, r: Self) -> Self {
+        let (sl, sr) = (l.size(), r.size());
+        if sl + sr < 2 {
+            Self::loser(k, p, l, m, r)
+        } else if sr > OMEGA * sl {
+            Self::balance_left(k, p, l, m, r)
+        } else if sl > OMEGA * sr {
+            Self::balance_right(k, p, l, m, r)
+        } else {
+            Self::loser(k, p, l, m, r)
+        }
+    }
+    fn balance_left(k: K, p: P, l: Self, m: K, r: Self) -> Self {
+        match r.0.as_ref() {
+            Node::Loser(_, _, _, rl, _, rr) if rl.size() < rr.size() => {
+                Self::single_left(k, p, l, m, r)
+            }
+            _ => Self::double_left(k, p, l, m, r),
+        }
+    }
+    fn balance_right(k: K, p: P, l: Self, m: K, r: Self) -> Self {
+        match l.0.as_ref() {
+            Node::Loser(_, _, _, ll, _, lr) if lr.size() < ll.size() => {
+                Self::single_right(k, p, l, m, r)
+            }
+            _ => Self::double_right(k, p, l, m, r),
+        }
+    }
+    fn single_left(k1: K, p1: P, t1: Self, m1: K, t: Self) -> Self {
+        match t.0.as_ref() {
+            Node::Start => unreachable!(),
+            Node::Loser(_, k2, p2, t2, m2, t3) => {
+                let (k2, p2, t2, m2, t3) =
+                    (k2.clone(), p2.clone(), t2.clone(), m2.clone(), t3.clone());
+                if k2 <= m2 && p1 <= p2 {
+                    Self::loser(k1, p1, Self::loser(k2, p2, t1, m1, t2), m2, t3)
+                } else {
+                    Self::loser(k2, p2, Self::loser(k1, p1, t1, m1, t2), m2, t3)
+                }
+            }
+        }
+    }
+    fn single_right(k1: K, p1: P, t: Self, m2: K, t3: Self) -> Self {
+        match t.0.as_ref() {
+            Node::Start => unreachable!(),
+            Node::Loser(_, k2, p2, t1, m1, t2) => {
+                let (k2, p2, t1, m1, t2) =
+                    (k2.clone(), p2.clone(), t1.clone(), m1.clone(), t2.clone());
+                if k2 > m1 && p1 <= p2 {
+                    Self::loser(k1, p1, t1, m1, Self::loser(k2, p2, t2, m2, t3))
+                } else {
+                    Self::loser(k2, p2, t1, m1, Self::loser(k1, p1, t2, m2, t3))
+                }
+            }
+        }
+    }
+    fn double_left(k1: K, p1: P, t1: Self, m1: K, t: Self) -> Self {
+        match t.0.as_ref() {
+            Node::Start => unreachable!(),
+            Node::Loser(_, k2, p2, t2, m2, t3) => {
+                let r =
+                    Self::single_right(k2.clone(), p2.clone(), t2.clone(), m2.clone(), t3.clone());
+                Self::single_left(k1, p1, t1, m1, r)
+            }
+        }
+    }
+    fn double_right(k1: K, p1: P, t: Self, m2: K, t3: Self) -> Self {
+        match t.0.as_ref() {
+            Node::Start => unreachable!(),
+            Node::Loser(_, k2, p2, t1, m1, t2) => {
+                let l =
+                    Self::single_left(k2.clone(), p2.clone(), t1.clone(), m1.clone(), t2.clone());
+                Self::single_right(k1, p1, l, m2, t3)
+            }
+        }
+    }
+}
+
+impl<K: Clone + Ord, P: Clone + Ord> PrioritySearchQueue<K, P> {
+    pub fn empty() -> Self {
+        Self(None)
+    }
+    pub fn is_empty(&self) -> bool {
+        self.0.is_none()
+    }
+    /// O(1)
+    pub fn len(&self) -> usize {
+        match &self.0 {
+            None => 0,
+            Some(Winner(_, _, t, _)) => t.size() + 1,
+        }
+    }
+    pub fn singleton(k: K, p: P) -> Self {
+        Self(Some(Winner(k.clone(), p, LTree::start(), k)))
+    }
+    fn max_key(&self) -> Option<&K> {
+        self.0.as_ref().map(|Winner(_, _, _, m)| m)
+    }
+    /// match of two tournaments where all keys of `self` precede those of `other`
+    fn play(&self, other: &Self) -> Self {
+        match (&self.0, &other.0) {
+            (None, _) => other.clone(),
+            (_, None) => self.clone(),
+            (Some(Winner(k, p, t, m)), Some(Winner(kp, pp, tp, mp))) => {
+                let (k, p, t, m) = (k.clone(), p.clone(), t.clone(), m.clone());
+                let (kp, pp, tp, mp) = (kp.clone(), pp.clone(), tp.clone(), mp.clone());
+                if p <= pp {
+                    Self(Some(Winner(k, p, LTree::balance(kp, pp, t, m, tp), mp)))
+                } else {
+                    Self(Some(Winner(kp, pp, LTree::balance(k, p, t, m, tp), mp)))
+                }
+            }
+        }
+    }
+    fn view(&self) -> View<K, P> {
+        match &self.0 {
+            None => View::Null,
+            Some(Winner(k, p, t, mp)) => match t.0.as_ref() {
+                Node::Start => View::Single(k.clone(), p.clone()),
+                Node::Loser(_, kl, pl, tl, m, tr) => {
+                    let (winner, loser) = ((k, p), (kl, pl));
+                    let (left, right) = if kl <= m {
+                        (loser, winner)
+                    } else {
+                        (winner, loser)
+                    };
+                    View::Play(
+                        Self(Some(Winner(
+                            left.0.clone(),
+                            left.1.clone(),
+                            tl.clone(),
+                            m.clone(),
+                        ))),
+                        Self(Some(Winner(
+                            right.0.clone(),
+                            right.1.clone(),
+                            tr.clone(),
+                            mp.clone(),
+                        ))),
+                    )
+                }
+            },
+        }
+    }
+    /// O(log n)
+    pub fn lookup(&self, k: &K) -> Option<P> {
+        match self.view() {
+            View::Null => None,
+            View::Single(kp, p) => (*k == kp).then_some(p),
+            View::Play(l, r) => {
+                if Some(k) <= l.max_key() {
+                    l.lookup(k)
+                } else {
+                    r.lookup(k)
+                }
+            }
+        }
+    }
+    /// insert a binding, replacing the priority of an existing key
+    pub fn insert(&self, k: K, p: P) -> Self {
+        match self.view() {
+            View::Null => Self::singleton(k, p),
+            View::Single(kp, pp) => match k.cmp(&kp) {
+                Ordering::Less => Self::singleton(k, p).play(&Self::singleton(kp, pp)),
+                Ordering::Equal => Self::singleton(k, p),
+                Ordering::Greater => Self::singleton(kp, pp).play(&Self::singleton(k, p)),
+            },
+            View::Play(l, r) => {
+                if Some(&k) <= l.max_key() {
+                    l.insert(k, p).play(&r)
+                } else {
+                    l.play(&r.insert(k, p))
+                }
+            }
+        }
+    }
+    /// update the priority of `k` by `f` if present
+    pub fn adjust<F: FnOnce(P) -> P>(&self, k: &K, f: F) -> Self {
+        match self.view() {
+            View::Null => self.clone(),
+            View::Single(kp, p) => {
+                if *k == kp {
+                    Self::singleton(kp, f(p))
+                } else {
+                    self.clone()
+                }
+            }
+            View::Play(l, r) => {
+                if Some(k) <= l.max_key() {
+                    l.adjust(k, f).play(&r)
+                } else {
+                    l.play(&r.adjust(k, f))
+                }
+            }
+        }
+    }
+    /// remove the binding of `k` if present
+    pub fn delete(&self, k: &K) -> Self {
+        match self.view() {
+            View::Null => self.clone(),
+            View::Single(kp, _) => {
+                if *k == kp {
+                    Self::empty()
+                } else {
+                    self.clone()
+                }
+            }
+            View::Play(l, r) => {
+                if Some(k) <= l.max_key() {
+                    l.delete(k).play(&r)
+                } else {
+                    l.play(&r.delete(k))
+                }
+            }
+        }
+    }
+    /// binding with the smallest priority, O(1)
+    pub fn find_min(&self) -> Option<(K, P)> {
+        self.0
+            .as_ref()
+            .map(|Winner(k, p, _, _)| (k.clone(), p.clone()))
+    }
+    pub fn delete_min(&self) -> Result<Self> {
+        match &self.0 {
+            None => bail!("empty"),
+            Some(Winner(_, _, t, m)) => Ok(Self::second_best(t, m)),
+        }
+    }
+    /// tournament of the losers, whose keys are at most `mp`
+    fn second_best(t: &LTree<K, P>, mp: &K) -> Self {
+        match t.0.as_ref() {
+            Node::Start => Self::empty(),
+            Node::Loser(_, k, p, tl, m, tr) => {
+                if k <= m {
+                    Self(Some(Winner(k.clone(), p.clone(), tl.clone(), m.clone())))
+                        .play(&Self::second_best(tr, mp))
+                } else {
+                    Self::second_best(tl, m).play(&Self(Some(Winner(
+                        k.clone(),
+                        p.clone(),
+                        tr.clone(),
+                        mp.clone(),
+                    ))))
+                }
+            }
+        }
+    }
+    /// bindings with priority at most `pt` in key order, O(r log n) for r results
+    pub fn at_most(&self, pt: &P) -> Vec<(K, P)> {
+        fn rec<K: Clone + Ord, P: Clone + Ord>(
+            q: &PrioritySearchQueue<K, P>,
+            pt: &P,
+            acc: &mut Vec<(K, P)>,
+        ) {
+            match &q.0 {
+                Some(Winner(_, p, _, _)) if p <= pt => {}
+                _ => return,
+            }
+            match q.view() {
+                View::Null => {}
+                View::Single(k, p) => acc.push((k, p)),
+                View::Play(l, r) => {
+                    rec(&l, pt, acc);
+                    rec(&r, pt, acc);
+                }
+            }
+        }
+
+        let mut acc = vec![];
+        rec(self, pt, &mut acc);
+        acc
+    }
+    /// all bindings in key order
+    pub fn to_vec(&self) -> Vec<(K, P)> {
+        let mut acc = vec![];
+        let mut stack = vec![self.clone()];
+        while let Some(q) = stack.pop() {
+            match q.view() {
+                View::Null => {}
+                View::Single(k, p) => acc.push((k, p)),
+                View::Play(l, r) => {
+                    stack.push(r);
+                    stack.push(l);
+                }
+            }
+        }
+        acc
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::prelude::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test() {
+        let q = [('d', 4), ('a', 3), ('c', 1), ('b', 5), ('e', 2)]
+            .into_iter()
+            .fold(PrioritySearchQueue::empty(), |q, (k, p)| q.insert(k, p));
+        assert_eq!(q.len(), 5);
+        assert_eq!(q.find_min(), Some(('c', 1)));
+        assert_eq!(q.lookup(&'b'), Some(5));
+        assert_eq!(q.lookup(&'z'), None);
+        assert_eq!(q.at_most(&3), vec![('a', 3), ('c', 1), ('e', 2)]);
+
+        let q1 = q.adjust(&'b', |_| 0).delete(&'c');
+        assert_eq!(q1.find_min(), Some(('b', 0)));
+        assert_eq!(q1.lookup(&'c'), None);
+        let q1 = q1.delete_min().unwrap();
+        assert_eq!(q1.find_min(), Some(('e', 2)));
+        assert_eq!(q1.to_vec(), vec![('a', 3), ('d', 4), ('e', 2)]);
+
+        assert_eq!(q.find_min(), Some(('c', 1)));
+        assert!(PrioritySearchQueue::<i32, i32>::empty()
+            .delete_min()
+            .is_err());
+    }
+
+    fn depth<K, P>(t: &LTree<K, P>) -> usize {
+        match t.0.as_ref() {
+            Node::Start => 0,
+            Node::Loser(_, _, _, l, _, r) => 1 + depth(l).max(depth(r)),
+        }
+    }
+
+    #[test]
+    fn test_balanced() {
+        let q = (0..4096).fold(PrioritySearchQueue::empty(), |q, x| q.insert(x, x % 7));
+        let Some(Winner(_, _, t, _)) = &q.0 else {
+            panic!()
+        };
+        assert!(depth(t) <= 24, "depth {}", depth(t));
+        assert_eq!(q.delete(&0).len(), 4095);
+    }
+
+    #[test]
+    fn test_random() {
+        let mut rng = StdRng::seed_from_u64(38);
+        let mut q = PrioritySearchQueue::empty();
+        let mut oracle = BTreeMap::new();
+        for _ in 0..3000 {
+            let k = rng.gen_range(0..200);
+            match rng.gen_range(0..5) {
+                0 | 1 => {
+                    let p = rng.gen_range(0..1000);
+                    q = q.insert(k, p);
+                    oracle.insert(k, p);
+                }
+                2 => {
+                    q = q.delete(&k);
+                    oracle.remove(&k);
+                }
+                3 => {
+                    q = q.adjust(&k, |p| p / 2);
+                    if let Some(p) = oracle.get_mut(&k) {
+                        *p /= 2;
+                    }
+                }
+                _ => {
+                    if let Some((k, p)) = q.find_min() {
+                        assert_eq!(oracle.remove(&k), Some(p));
+                        q = q.delete_min().unwrap();
+                    }
+                }
+            }
+            assert_eq!(q.len(), oracle.len());
+            assert_eq!(q.lookup(&k), oracle.get(&k).copied());
+            assert_eq!(q.find_min().map(|(_, p)| p), oracle.values().min().copied());
+        }
+        assert_eq!(q.to_vec(), oracle.clone().into_iter().collect::<Vec<_>>());
+        assert_eq!(
+            q.at_most(&100),
+            oracle
+                .into_iter()
+                .filter(|&(_, p)| p <= 100)
+                .collect::<Vec<_>>()
+        );
+    }
+}
@@ -1,22 +1,66 @@
 pub mod bankers_queue;
+pub mod bootstrapped_queue;
 pub mod hood_melville_queue;
+pub mod implicit_queue;
 pub mod naive_queue;
 pub mod physicists_queue;
 pub mod real_time_queue;
 
 pub trait Queue<T>
 where
-    Self: Sized,
+    Self: Sized + Clone,
 {
     fn empty() -> Self;
     fn is_empty(&self) -> bool;
     fn snoc(&self, x: T) -> Self;
     fn head(&self) -> Option<T>;
     fn tail(&self) -> Option<Self>;
+
+    /// number of elements, O(n) by traversal unless overridden
+    fn len(&self) -> usize {
+        self.iter().count()
+    }
+    /// elements from the head, without draining
+    fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        let mut q = self.clone();
+        std::iter::from_fn(move || {
+            let (x, tail) = q.uncons()?;
+            q = tail;
+            Some(x)
+        })
+    }
+    fn uncons(&self) -> Option<(T, Self)> {
+        Some((self.head()?, self.tail()?))
+    }
+    fn snoc_all<I: IntoIterator<Item = T>>(&self, xs: I) -> Self {
+        xs.into_iter().fold(self.clone(), |q, x| q.snoc(x))
+    }
+    /// elements of `self` followed by those of `other`, O(|other|) unless overridden
+    fn append(&self, other: &Self) -> Self {
+        self.snoc_all(other.iter())
+    }
+    fn from_iter<I: IntoIterator<Item = T>>(xs: I) -> Self {
+        Self::empty().snoc_all(xs)
+    }
+}
+
+/// Type constructor of a queue, for structures holding queues of their own nodes
+pub trait QueueFamily: 'static {
+    type Queue<U: Clone + 'static>: Queue<U> + Clone;
 }
 
 macro_rules! impl_queue {
-    ($q:ident) => {
+    ($q:ident, $f:ident) => {
+        $crate::queue::impl_queue!($q, $f, {});
+    };
+    ($q:ident, $f:ident, { $($body:tt)* }) => {
+        #[doc = concat!("`QueueFamily` of `", stringify!($q), "`")]
+        pub struct $f;
+
+        impl $crate::queue::QueueFamily for $f {
+            type Queue<U: Clone + 'static> = $q<U>;
+        }
+
         impl<T: Clone + 'static> $crate::queue::Queue<T> for $q<T> {
             fn empty() -> Self {
                 $q::empty()
@@ -33,8 +77,100 @@ macro_rules! impl_queue {
             fn tail(&self) -> Option<Self> {
                 $q::tail(self)
             }
+            $($body)*
         }
     };
 }
 
 pub(crate) use impl_queue;
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        bankers_queue::BankersQueue, bootstrapped_queue::BootstrappedQueue,
+        hood_melville_queue::HoodMelvilleQueue, implicit_queue::ImplicitQueue,
+        naive_queue::BatchedQueue, physicists_queue::PhysicistsQueue,
+        real_time_queue::RealTimeQueue, *,
+    };
+    use rand::prelude::*;
+
+    fn check<Q: Queue<i32>>() {
+        let q = Q::from_iter(0..10);
+        assert_eq!(q.len(), 10);
+        assert_eq!(q.iter().collect::<Vec<_>>(), (0..10).collect::<Vec<_>>());
+        let (x, q) = q.uncons().unwrap();
+        assert_eq!(x, 0);
+        let q = q.snoc_all(10..15);
+        assert_eq!(q.len(), 14);
+        assert_eq!(q.iter().collect::<Vec<_>>(), (1..15).collect::<Vec<_>>());
+
+        // rear elements are iterated in order too
+        let r = (20..25).fold(Q::from_iter([19]), |q, x| q.snoc(x));
+        let qr = q.append(&r);
+        assert_eq!(qr.len(), 20);
+        assert_eq!(
+            qr.iter().collect::<Vec<_>>(),
+            (1..15).chain(19..25).collect::<Vec<_>>()
+        );
+        // draining agrees with the iterator
+        let mut drained = vec![];
+        let mut qr = qr;
+        while let Some((x, tail)) = qr.uncons() {
+            drained.push(x);
+            qr = tail;
+        }
+        assert_eq!(drained, (1..15).chain(19..25).collect::<Vec<_>>());
+        assert!(qr.is_empty());
+        assert!(qr.tail().is_none());
+        assert_eq!(Q::empty().append(&q).len(), 14);
+        assert_eq!(q.append(&Q::empty()).len(), 14);
+    }
+
+    #[test]
+    fn test_defaults() {
+        check::<BankersQueue<_>>();
+        check::<BootstrappedQueue<_>>();
+        check::<HoodMelvilleQueue<_>>();
+        check::<ImplicitQueue<_>>();
+        check::<BatchedQueue<_>>();
+        check::<PhysicistsQueue<_>>();
+        check::<RealTimeQueue<_>>();
+    }
+
+    /// random operations compared with `BankersQueue`, checking some older versions at the end
+    fn check_against_bankers<Q: Queue<i32>>(seed: u64) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut q = Q::empty();
+        let mut oracle = BankersQueue::empty();
+        let mut versions = vec![];
+        for x in 0..5000 {
+            if rng.gen_bool(0.6) || oracle.is_empty() {
+                q = q.snoc(x);
+                oracle = oracle.snoc(x);
+            } else {
+                q = q.tail().unwrap();
+                oracle = oracle.tail().unwrap();
+            }
+            assert_eq!(q.head(), oracle.head());
+            assert_eq!(q.is_empty(), oracle.is_empty());
+            if x % 500 == 0 {
+                versions.push((q.clone(), oracle.clone()));
+            }
+        }
+        // older versions are still valid
+        for (q, oracle) in versions {
+            assert_eq!(q.len(), oracle.len());
+            assert!(q.iter().eq(oracle.iter()));
+        }
+    }
+
+    #[test]
+    fn test_against_bankers() {
+        check_against_bankers::<BootstrappedQueue<_>>(44);
+        check_against_bankers::<HoodMelvilleQueue<_>>(43);
+        check_against_bankers::<ImplicitQueue<_>>(41);
+        check_against_bankers::<BatchedQueue<_>>(40);
+        check_against_bankers::<PhysicistsQueue<_>>(42);
+        check_against_bankers::<RealTimeQueue<_>>(45);
+    }
+}
@@ -20,6 +20,10 @@ impl<T: Clone + 'static> BankersQueue<T> {
     pub fn is_empty(&self) -> bool {
         self.lenf == 0
     }
+    /// O(1)
+    pub fn len(&self) -> usize {
+        self.lenf + self.lenr
+    }
     fn check(self) -> Self {
         if self.lenr <= self.lenf {
             self
@@ -46,6 +50,9 @@ impl<T: Clone + 'static> BankersQueue<T> {
         self.f.head().force()
     }
     pub fn tail(&self) -> Option<Self> {
+        if self.is_empty() {
+            return None;
+        }
         Some(
             Self {
                 lenf: self.lenf - 1,
@@ -56,9 +63,47 @@ impl<T: Clone + 'static> BankersQueue<T> {
             .check(),
         )
     }
+    pub fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        let rear = self.r.iter().collect::<Vec<_>>();
+        self.f.iter().chain(rear.into_iter().rev())
+    }
+    /// O(1) to create, suspending the reversal of both rear streams; each reversal costs
+    /// O(m) in the length of its rear once reached
+    pub fn append(&self, other: &Self) -> Self {
+        let f = self.f.extend(&self.r.reverse());
+        let g = other.f.extend(&other.r.reverse());
+        Self {
+            lenf: self.len() + other.len(),
+            f: f.extend(&g),
+            lenr: 0,
+            r: Stream::empty(),
+        }
+    }
 }
 
-crate::queue::impl_queue!(BankersQueue);
+crate::queue::impl_queue!(BankersQueue, BankersQueueFamily, {
+    fn len(&self) -> usize {
+        BankersQueue::len(self)
+    }
+    fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        BankersQueue::iter(self)
+    }
+    fn append(&self, other: &Self) -> Self {
+        BankersQueue::append(self, other)
+    }
+    fn from_iter<I: IntoIterator<Item = T>>(xs: I) -> Self {
+        let xs = xs.into_iter().collect::<Vec<_>>();
+        Self {
+            lenf: xs.len(),
+            f: xs
+                .into_iter()
+                .rev()
+                .fold(Stream::empty(), |s, x| Stream::cons(lazy!((x, s)))),
+            lenr: 0,
+            r: Stream::empty(),
+        }
+    }
+});
 
 #[cfg(test)]
 mod tests {
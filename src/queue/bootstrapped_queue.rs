@@ -0,0 +1,138 @@
+use std::sync::Arc;
+
+use crate::{lazy, lazy::Lazy, List};
+
+/// Queue by structural decomposition (section 10.1.3) with O(log* n) amortized operations.
+/// The middle is a queue of suspended reversed rear lists; since it would be
+/// `BootstrappedQueue<Lazy<List<T>>>` by polymorphic recursion, every level instead
+/// stores the erased `Elem<T>`.
+#[derive(Clone)]
+pub struct BootstrappedQueue<T>(Arc<Node<T>>);
+
+/// element at some depth of the queue: an item at the top and a suspended list below
+#[derive(Clone)]
+enum Elem<T> {
+    Item(T),
+    Lst(Lazy<List<Elem<T>>>),
+}
+
+enum Node<T> {
+    Empty,
+    /// length of the front list and the middle lists, non-empty front list, middle queue,
+    /// length of the rear list and the rear list
+    Queue(
+        usize,
+        List<Elem<T>>,
+        BootstrappedQueue<T>,
+        usize,
+        List<Elem<T>>,
+    ),
+}
+
+impl<T> From<Node<T>> for BootstrappedQueue<T> {
+    fn from(x: Node<T>) -> Self {
+        Self(Arc::new(x))
+    }
+}
+
+impl<T: Clone + 'static> BootstrappedQueue<T> {
+    pub fn empty() -> Self {
+        Node::Empty.into()
+    }
+    pub fn is_empty(&self) -> bool {
+        matches!(self.0.as_ref(), Node::Empty)
+    }
+    /// O(1)
+    pub fn len(&self) -> usize {
+        match self.0.as_ref() {
+            Node::Empty => 0,
+            Node::Queue(lenfm, _, _, lenr, _) => lenfm + lenr,
+        }
+    }
+    pub fn snoc(&self, x: T) -> Self {
+        self.snoc_elem(Elem::Item(x))
+    }
+    pub fn head(&self) -> Option<T> {
+        match self.head_elem()? {
+            Elem::Item(x) => Some(x),
+            Elem::Lst(_) => unreachable!(),
+        }
+    }
+    pub fn tail(&self) -> Option<Self> {
+        match self.0.as_ref() {
+            Node::Empty => None,
+            Node::Queue(lenfm, f, m, lenr, r) => Some(Self::check_q(
+                lenfm - 1,
+                f.tail().unwrap(),
+                m.clone(),
+                *lenr,
+                r.clone(),
+            )),
+        }
+    }
+    fn check_q(lenfm: usize, f: List<Elem<T>>, m: Self, lenr: usize, r: List<Elem<T>>) -> Self {
+        if lenr <= lenfm {
+            Self::check_f(lenfm, f, m, lenr, r)
+        } else {
+            let m = m.snoc_elem(Elem::Lst(lazy!(r.reverse())));
+            Self::check_f(lenfm + lenr, f, m, 0, List::empty())
+        }
+    }
+    fn check_f(lenfm: usize, f: List<Elem<T>>, m: Self, lenr: usize, r: List<Elem<T>>) -> Self {
+        if !f.is_empty() {
+            return Node::Queue(lenfm, f, m, lenr, r).into();
+        }
+        match m.head_elem() {
+            None => Self::empty(),
+            Some(Elem::Lst(f)) => Node::Queue(lenfm, f.force(), m.tail().unwrap(), lenr, r).into(),
+            Some(Elem::Item(_)) => unreachable!(),
+        }
+    }
+    fn snoc_elem(&self, x: Elem<T>) -> Self {
+        match self.0.as_ref() {
+            Node::Empty => Node::Queue(
+                1,
+                List::cons(x, List::empty()),
+                Self::empty(),
+                0,
+                List::empty(),
+            )
+            .into(),
+            Node::Queue(lenfm, f, m, lenr, r) => Self::check_q(
+                *lenfm,
+                f.clone(),
+                m.clone(),
+                lenr + 1,
+                List::cons(x, r.clone()),
+            ),
+        }
+    }
+    fn head_elem(&self) -> Option<Elem<T>> {
+        match self.0.as_ref() {
+            Node::Empty => None,
+            Node::Queue(_, f, ..) => f.head(),
+        }
+    }
+}
+
+crate::queue::impl_queue!(BootstrappedQueue, BootstrappedQueueFamily, {
+    fn len(&self) -> usize {
+        BootstrappedQueue::len(self)
+    }
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test() {
+        let mut q = (0..100).fold(BootstrappedQueue::empty(), |q, x| q.snoc(x));
+        for x in 0..100 {
+            assert_eq!(q.head(), Some(x));
+            q = q.tail().unwrap();
+        }
+        assert!(q.is_empty());
+        assert!(q.tail().is_none());
+    }
+}
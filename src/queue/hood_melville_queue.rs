@@ -2,6 +2,10 @@ use crate::{stack::Cell, List};
 
 use super::impl_queue;
 
+/// Hood-Melville real-time queue (section 8.2.1). Its rotation stays a `RotationState`
+/// rather than a `crate::rebuild::Rebuild` job: `snoc` goes to the new rear outside the
+/// rotation and `tail` only drops elements off the front of its result, which the `ok`
+/// count undoes in place, so there is nothing to record and replay.
 #[derive(Clone)]
 pub struct HoodMelvilleQueue<T> {
     lenf: usize,
@@ -132,6 +136,10 @@ impl<T: Clone> HoodMelvilleQueue<T> {
     pub fn is_empty(&self) -> bool {
         self.lenf == 0
     }
+    /// O(1)
+    pub fn len(&self) -> usize {
+        self.lenf + self.lenr
+    }
     pub fn snoc(&self, x: T) -> Self {
         Self {
             lenf: self.lenf,
@@ -146,6 +154,9 @@ impl<T: Clone> HoodMelvilleQueue<T> {
         self.f.head()
     }
     pub fn tail(&self) -> Option<Self> {
+        if self.is_empty() {
+            return None;
+        }
         Some(
             Self {
                 lenf: self.lenf - 1,
@@ -159,7 +170,11 @@ impl<T: Clone> HoodMelvilleQueue<T> {
     }
 }
 
-impl_queue!(HoodMelvilleQueue);
+impl_queue!(HoodMelvilleQueue, HoodMelvilleQueueFamily, {
+    fn len(&self) -> usize {
+        HoodMelvilleQueue::len(self)
+    }
+});
 
 #[cfg(test)]
 mod tests {
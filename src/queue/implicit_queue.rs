@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use crate::{lazy, lazy::Lazy};
+
+/// Queue by implicit recursive slowdown (section 11.1) with O(1) amortized operations.
+/// The middle queue holds pairs of elements; since it would be `ImplicitQueue<(T, T)>`
+/// by polymorphic recursion, every level instead stores the erased `Elem<T>`.
+#[derive(Clone)]
+pub struct ImplicitQueue<T>(Arc<Node<T>>);
+
+/// element at some depth of the queue: a leaf at the top, `2^d` leaves at depth `d`
+#[derive(Clone)]
+enum Elem<T> {
+    Leaf(T),
+    Pair(Arc<(Elem<T>, Elem<T>)>),
+}
+
+#[derive(Clone)]
+enum Digit<T> {
+    Zero,
+    One(Elem<T>),
+    Two(Elem<T>, Elem<T>),
+}
+
+enum Node<T> {
+    Shallow(Digit<T>),
+    /// front digit of one or two, middle queue of pairs, rear digit of zero or one
+    Deep(Digit<T>, Lazy<ImplicitQueue<T>>, Digit<T>),
+}
+
+impl<T> From<Node<T>> for ImplicitQueue<T> {
+    fn from(x: Node<T>) -> Self {
+        Self(Arc::new(x))
+    }
+}
+
+impl<T: Clone + 'static> ImplicitQueue<T> {
+    pub fn empty() -> Self {
+        Node::Shallow(Digit::Zero).into()
+    }
+    pub fn is_empty(&self) -> bool {
+        matches!(self.0.as_ref(), Node::Shallow(Digit::Zero))
+    }
+    pub fn snoc(&self, x: T) -> Self {
+        self.snoc_elem(Elem::Leaf(x))
+    }
+    pub fn head(&self) -> Option<T> {
+        match self.head_elem()? {
+            Elem::Leaf(x) => Some(x),
+            Elem::Pair(_) => unreachable!(),
+        }
+    }
+    pub fn tail(&self) -> Option<Self> {
+        Some(match self.0.as_ref() {
+            Node::Shallow(Digit::Zero) => return None,
+            Node::Shallow(_) => Self::empty(),
+            Node::Deep(Digit::Two(_, y), m, r) => {
+                Node::Deep(Digit::One(y.clone()), m.clone(), r.clone()).into()
+            }
+            Node::Deep(_, m, r) => {
+                let q = m.force();
+                match q.head_elem() {
+                    None => Node::Shallow(r.clone()).into(),
+                    Some(Elem::Pair(yz)) => {
+                        let (y, z) = yz.as_ref().clone();
+                        Node::Deep(Digit::Two(y, z), lazy!(q.tail().unwrap()), r.clone()).into()
+                    }
+                    Some(Elem::Leaf(_)) => unreachable!(),
+                }
+            }
+        })
+    }
+    fn snoc_elem(&self, y: Elem<T>) -> Self {
+        match self.0.as_ref() {
+            Node::Shallow(Digit::Zero) => Node::Shallow(Digit::One(y)).into(),
+            Node::Shallow(Digit::One(x)) => {
+                Node::Deep(Digit::Two(x.clone(), y), lazy!(Self::empty()), Digit::Zero).into()
+            }
+            Node::Deep(f, m, Digit::Zero) => Node::Deep(f.clone(), m.clone(), Digit::One(y)).into(),
+            Node::Deep(f, m, Digit::One(x)) => {
+                let m = m.clone();
+                let xy = Elem::Pair(Arc::new((x.clone(), y)));
+                Node::Deep(f.clone(), lazy!(m.force().snoc_elem(xy)), Digit::Zero).into()
+            }
+            _ => unreachable!(),
+        }
+    }
+    fn head_elem(&self) -> Option<Elem<T>> {
+        match self.0.as_ref() {
+            Node::Shallow(Digit::Zero) => None,
+            Node::Shallow(Digit::One(x)) => Some(x.clone()),
+            Node::Deep(Digit::One(x) | Digit::Two(x, _), _, _) => Some(x.clone()),
+            _ => unreachable!(),
+        }
+    }
+}
+
+crate::queue::impl_queue!(ImplicitQueue, ImplicitQueueFamily);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test() {
+        let q = (0..10).fold(ImplicitQueue::empty(), |q, x| q.snoc(x));
+        let mut q = q;
+        for x in 0..10 {
+            assert_eq!(q.head(), Some(x));
+            q = q.tail().unwrap();
+        }
+        assert!(q.is_empty());
+        assert!(q.tail().is_none());
+    }
+}
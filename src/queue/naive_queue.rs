@@ -32,9 +32,26 @@ impl<T: Clone> BatchedQueue<T> {
     pub fn tail(&self) -> Option<Self> {
         Some(Self(self.0.clone().tail()?, self.1.clone()).checkf())
     }
+
+    pub fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        let rear = self.1.iter().cloned().collect::<Vec<_>>();
+        self.0.iter().cloned().chain(rear.into_iter().rev())
+    }
 }
 
-crate::queue::impl_queue!(BatchedQueue);
+crate::queue::impl_queue!(BatchedQueue, BatchedQueueFamily, {
+    fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        BatchedQueue::iter(self)
+    }
+    fn from_iter<I: IntoIterator<Item = T>>(xs: I) -> Self {
+        let xs = xs.into_iter().collect::<Vec<_>>();
+        let f = xs
+            .into_iter()
+            .rev()
+            .fold(List::empty(), |f, x| List::cons(x, f));
+        Self(f, List::empty())
+    }
+});
 
 #[cfg(test)]
 mod tests {
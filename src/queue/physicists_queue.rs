@@ -2,6 +2,7 @@ use crate::{lazy, lazy::Lazy, List};
 
 use super::impl_queue;
 
+#[derive(Clone)]
 pub struct PhysicistsQueue<T> {
     w: List<T>,
     lenf: usize,
@@ -23,6 +24,10 @@ impl<T: Clone + 'static> PhysicistsQueue<T> {
     pub fn is_empty(&self) -> bool {
         self.lenf == 0
     }
+    /// O(1)
+    pub fn len(&self) -> usize {
+        self.lenf + self.lenr
+    }
     fn checkw(self) -> Self {
         if self.w.is_empty() {
             Self {
@@ -77,9 +82,64 @@ impl<T: Clone + 'static> PhysicistsQueue<T> {
             .check(),
         )
     }
+    pub fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        let rear = self.r.iter().cloned().collect::<Vec<_>>();
+        let front = self.f.force();
+        let front = front.iter().cloned().collect::<Vec<_>>();
+        front.into_iter().chain(rear.into_iter().rev())
+    }
+    /// O(1) to create, suspending the concatenation of both queues, which costs O(m + n)
+    /// amortized over the elements it serves once forced. An empty side is returned as is
+    /// since refilling the working copy of an empty `self` would force the whole of `other`.
+    pub fn append(&self, other: &Self) -> Self {
+        if self.is_empty() {
+            return other.clone();
+        }
+        if other.is_empty() {
+            return self.clone();
+        }
+        let (this, other) = (self.clone(), other.clone());
+        Self {
+            w: self.w.clone(),
+            lenf: self.len() + other.len(),
+            f: lazy!(this
+                .f
+                .force()
+                .extend(this.r.reverse())
+                .extend(other.f.force().extend(other.r.reverse()))),
+            lenr: 0,
+            r: List::empty(),
+        }
+        .checkw()
+    }
 }
 
-impl_queue!(PhysicistsQueue);
+impl_queue!(PhysicistsQueue, PhysicistsQueueFamily, {
+    fn len(&self) -> usize {
+        PhysicistsQueue::len(self)
+    }
+    fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        PhysicistsQueue::iter(self)
+    }
+    fn append(&self, other: &Self) -> Self {
+        PhysicistsQueue::append(self, other)
+    }
+    fn from_iter<I: IntoIterator<Item = T>>(xs: I) -> Self {
+        let xs = xs.into_iter().collect::<Vec<_>>();
+        let lenf = xs.len();
+        let f = xs
+            .into_iter()
+            .rev()
+            .fold(List::empty(), |f, x| List::cons(x, f));
+        Self {
+            w: f.clone(),
+            lenf,
+            f: lazy!(f),
+            lenr: 0,
+            r: List::empty(),
+        }
+    }
+});
 
 #[cfg(test)]
 mod tests {
@@ -102,4 +162,20 @@ mod tests {
         let q = q.tail().unwrap();
         assert!(q.is_empty());
     }
+
+    #[test]
+    fn test_append_empty() {
+        // a front that must stay suspended
+        let q = PhysicistsQueue {
+            w: List::cons(1, List::cons(2, List::empty())),
+            lenf: 2,
+            f: Lazy::new(|| -> List<i32> { panic!("forced") }),
+            lenr: 0,
+            r: List::empty(),
+        };
+        let e = PhysicistsQueue::empty();
+        assert_eq!(e.append(&q).head(), Some(1));
+        assert_eq!(q.append(&e).head(), Some(1));
+        assert_eq!(e.append(&e).head(), None);
+    }
 }
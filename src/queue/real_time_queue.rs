@@ -56,9 +56,26 @@ impl<T: Clone + 'static> RealTimeQueue<T> {
             StreamCell::Cons(_, xs) => Some(Self(xs, self.1.clone(), self.2.clone()).exec()),
         }
     }
+    pub fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        let rear = self.1.iter().cloned().collect::<Vec<_>>();
+        self.0.iter().chain(rear.into_iter().rev())
+    }
 }
 
-impl_queue!(RealTimeQueue);
+impl_queue!(RealTimeQueue, RealTimeQueueFamily, {
+    fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        RealTimeQueue::iter(self)
+    }
+    /// the whole front is already its own schedule
+    fn from_iter<I: IntoIterator<Item = T>>(xs: I) -> Self {
+        let xs = xs.into_iter().collect::<Vec<_>>();
+        let f = xs
+            .into_iter()
+            .rev()
+            .fold(Stream::empty(), |s, x| Stream::cons(lazy!((x, s))));
+        Self(f.clone(), List::empty(), f)
+    }
+});
 
 #[cfg(test)]
 mod tests {
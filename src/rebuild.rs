@@ -0,0 +1,112 @@
+use std::ops::ControlFlow;
+
+use crate::queue::real_time_queue::RealTimeQueue;
+
+/// Incremental computation of a fresh copy of a structure for global rebuilding
+/// (section 8.1), like the rotation of `HoodMelvilleQueue` but over any steps
+pub trait Rebuild: Clone {
+    type Output;
+    /// one bounded piece of work, breaking with the fresh copy once it is built
+    fn step(self) -> ControlFlow<Self::Output, Self>;
+}
+
+/// Fresh copy that can catch up with an update applied to the working copy meanwhile
+pub trait Replay<U> {
+    fn replay(self, u: &U) -> Self;
+}
+
+/// Global rebuilding in the background of a working copy. Updates to the working copy are
+/// recorded while the job runs and replayed on its result, so that `exec` with enough
+/// steps per operation finishes before the working copy runs out.
+#[derive(Clone)]
+pub enum Rebuilding<R: Rebuild, U> {
+    Idle,
+    /// running the job and recording updates
+    Running(R, RealTimeQueue<U>),
+    /// replaying the recorded updates on the fresh copy, still recording new ones
+    Replaying(R::Output, RealTimeQueue<U>),
+    /// the fresh copy is up to date with the working copy
+    Done(R::Output),
+}
+
+impl<R, U> Rebuilding<R, U>
+where
+    R: Rebuild,
+    R::Output: Replay<U>,
+    U: Clone + 'static,
+{
+    pub fn start(job: R) -> Self {
+        Self::Running(job, RealTimeQueue::empty())
+    }
+    pub fn is_idle(&self) -> bool {
+        matches!(self, Self::Idle)
+    }
+    /// record an update applied to the working copy
+    pub fn record(self, u: U) -> Self {
+        match self {
+            Self::Idle => Self::Idle,
+            Self::Running(job, q) => Self::Running(job, q.snoc(u)),
+            Self::Replaying(x, q) => Self::Replaying(x, q.snoc(u)),
+            Self::Done(x) => Self::Done(x.replay(&u)),
+        }
+    }
+    /// perform at most `n` steps of the job or the replay
+    pub fn exec(self, n: usize) -> Self {
+        let mut state = self;
+        for _ in 0..n {
+            state = match state {
+                Self::Running(job, q) => match job.step() {
+                    ControlFlow::Continue(job) => Self::Running(job, q),
+                    ControlFlow::Break(x) => Self::Replaying(x, q),
+                },
+                Self::Replaying(x, q) => match q.head() {
+                    Some(u) => Self::Replaying(x.replay(&u), q.tail().unwrap()),
+                    None => Self::Done(x),
+                },
+                state => return state,
+            };
+        }
+        match state {
+            Self::Replaying(x, q) if q.is_empty() => Self::Done(x),
+            state => state,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::List;
+
+    /// sum of a list, one element per step
+    #[derive(Clone)]
+    struct Sum(List<i32>, i32);
+
+    impl Rebuild for Sum {
+        type Output = i32;
+        fn step(self) -> ControlFlow<i32, Self> {
+            match self.0.head() {
+                Some(x) => ControlFlow::Continue(Self(self.0.tail().unwrap(), self.1 + x)),
+                None => ControlFlow::Break(self.1),
+            }
+        }
+    }
+
+    impl Replay<i32> for i32 {
+        fn replay(self, u: &i32) -> Self {
+            self + u
+        }
+    }
+
+    #[test]
+    fn test() {
+        let xs = (1..=10).fold(List::empty(), |xs, x| List::cons(x, xs));
+        let state = Rebuilding::start(Sum(xs, 0)).exec(4).record(100);
+        assert!(matches!(state, Rebuilding::Running(..)));
+        let state = state.exec(7).record(1000);
+        assert!(matches!(state, Rebuilding::Replaying(..)));
+        let state = state.exec(2);
+        assert!(matches!(state, Rebuilding::Done(1155)));
+        assert!(matches!(state.record(1), Rebuilding::Done(1156)));
+    }
+}
@@ -32,7 +32,7 @@ impl<T> From<Arc<Tree<T>>> for RedBlackTree<T> {
     }
 }
 
-impl<T: Clone + PartialOrd> RedBlackTree<T> {
+impl<T: Clone + Ord> RedBlackTree<T> {
     pub fn empty() -> Self {
         Tree::Empty.into()
     }
@@ -67,7 +67,7 @@ impl<T: Clone + PartialOrd> RedBlackTree<T> {
     }
 }
 
-impl<T: Clone + PartialOrd> Tree<T> {
+impl<T: Clone + Ord> Tree<T> {
     fn node(color: Color, x: T, left: RedBlackTree<T>, right: RedBlackTree<T>) -> Arc<Self> {
         Arc::new(Self::Node {
             color,
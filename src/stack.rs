@@ -24,6 +24,27 @@ impl<T> From<Cell<T>> for Stack<T> {
     }
 }
 
+impl<T> Stack<T> {
+    /// move out the head and the tail if no other stack shares this cell, leaving `Nil`
+    pub(crate) fn take_unique(&mut self) -> Option<(T, Self)> {
+        match std::mem::replace(Arc::get_mut(&mut self.0)?, Cell::Nil) {
+            Cell::Nil => None,
+            Cell::Cons(x, xs) => Some((x, xs)),
+        }
+    }
+}
+
+/// drop the unshared prefix iteratively instead of recursing along the tail
+impl<T> Drop for Stack<T> {
+    fn drop(&mut self) {
+        if let Some((_, mut xs)) = self.take_unique() {
+            while let Some((_, tail)) = xs.take_unique() {
+                xs = tail;
+            }
+        }
+    }
+}
+
 impl<T: Clone> Stack<T> {
     pub fn empty() -> Self {
         Cell::Nil.into()
@@ -109,6 +130,25 @@ impl<T: Clone> Stack<T> {
 
         s(self, 0)
     }
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter(self)
+    }
+}
+
+/// Iterator over references to the elements of a `Stack`
+pub struct Iter<'a, T>(&'a Stack<T>);
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<&'a T> {
+        match self.0 .0.as_ref() {
+            Cell::Nil => None,
+            Cell::Cons(x, xs) => {
+                self.0 = xs;
+                Some(x)
+            }
+        }
+    }
 }
 
 impl<T: Clone + 'static> Stack<T> {
@@ -164,4 +204,19 @@ mod tests {
             50
         );
     }
+
+    #[test]
+    fn test_drop_long() {
+        let s = (0..1_000_000).fold(Stack::empty(), |s, x| Stack::cons(x, s));
+        let t = s.drop(10);
+        drop(s);
+        assert_eq!(t.head(), Some(999_989));
+    }
+
+    #[test]
+    fn test_iter() {
+        let s = Stack::cons(1, Stack::cons(2, Stack::cons(3, Stack::empty())));
+        assert_eq!(s.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(Stack::<i32>::empty().iter().next(), None);
+    }
 }
@@ -16,7 +16,7 @@ pub trait UnbalancedSet<T> {
     fn member(&self, val: &T) -> bool;
 }
 
-impl<T: Clone + PartialOrd> UnbalancedSet<T> for Tree<T> {
+impl<T: Clone + Ord> UnbalancedSet<T> for Tree<T> {
     fn empty() -> Self {
         Self(TreeImpl::empty())
     }
@@ -48,7 +48,7 @@ impl<T> From<Arc<TreeImpl<T>>> for Tree<T> {
     }
 }
 
-impl<T: Clone + PartialOrd> TreeImpl<T> {
+impl<T: Clone + Ord> TreeImpl<T> {
     fn empty() -> Arc<Self> {
         Arc::new(Self::Empty)
     }
@@ -105,7 +105,7 @@ impl<T: Clone + PartialOrd> TreeImpl<T> {
     /// exercise 2.4
     #[allow(dead_code)]
     fn insert_nodup2(self: Arc<Self>, x: T) -> Arc<Self> {
-        fn rec<T: Clone + PartialOrd>(
+        fn rec<T: Clone + Ord>(
             t: Arc<TreeImpl<T>>,
             x: T,
             z: Option<T>,
@@ -157,7 +157,7 @@ impl<T: Clone + PartialOrd> TreeImpl<T> {
     /// excercise 2.2
     #[allow(dead_code)]
     fn member2(&self, x: &T) -> bool {
-        fn rec<T: Clone + PartialOrd>(t: &TreeImpl<T>, x: &T, z: Option<&T>) -> bool {
+        fn rec<T: Clone + Ord>(t: &TreeImpl<T>, x: &T, z: Option<&T>) -> bool {
             match t {
                 TreeImpl::Empty => Some(x) == z,
                 TreeImpl::Node(y, left, right) => {
@@ -174,7 +174,7 @@ impl<T: Clone + PartialOrd> TreeImpl<T> {
     }
 }
 
-impl<T: Clone + PartialOrd> Tree<T> {
+impl<T: Clone + Ord> Tree<T> {
     /// exercise 2.5 (a)
     pub fn complete(x: T, depth: usize) -> Self {
         if depth == 0 {
@@ -191,7 +191,7 @@ impl<T: Clone + PartialOrd> Tree<T> {
             Self::empty()
         } else {
             let (a, b) = Self::create2(x.clone(), size / 2);
-            if size % 2 == 0 {
+            if size.is_multiple_of(2) {
                 TreeImpl::Node(x, a.clone(), a).into()
             } else {
                 TreeImpl::Node(x, a, b).into()
@@ -205,7 +205,7 @@ impl<T: Clone + PartialOrd> Tree<T> {
                 let e = Self::empty();
                 (e.clone(), TreeImpl::Node(x, e.clone(), e).into())
             }
-            _ if size % 2 == 0 => {
+            _ if size.is_multiple_of(2) => {
                 let (a, b) = Self::create2(x.clone(), size / 2);
                 (
                     TreeImpl::Node(x.clone(), a.clone(), a.clone()).into(),
@@ -230,7 +230,7 @@ pub trait FiniteMap<K, V> {
     fn lookup(&self, key: K) -> Option<V>;
 }
 
-impl<K: Clone + PartialOrd, V: Clone> FiniteMap<K, V> for Tree<(K, V)> {
+impl<K: Clone + Ord, V: Clone> FiniteMap<K, V> for Tree<(K, V)> {
     fn empty() -> Self {
         Self(Arc::new(TreeImpl::Empty))
     }